@@ -0,0 +1,297 @@
+//! Prime fields of arbitrary size, with the modulus chosen at runtime.
+
+use crate::biguint::BigUint;
+use crate::Field;
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+/// The prime field F_p for a prime of any size, created once and used to construct elements.
+///
+/// The modulus is not checked for primality, which is slow for thousands of bits;
+/// use `BigUint::is_probable_prime` when it is not known to be a prime.
+/// ```
+/// use galois_field::*;
+/// // 2^521 - 1
+/// let p = &(&BigUint::one() << 521) - &BigUint::one();
+/// let field = BigPrimeField::new(p);
+/// let x = field.elem(BigUint::from(3u64));
+/// assert_eq!(x.clone() * x.inv(), field.one());
+/// assert_eq!(-field.one(), field.elem(field.modulus() - &BigUint::one()));
+/// ```
+#[derive(Clone)]
+pub struct BigPrimeField {
+    modulus: Arc<BigUint>,
+}
+
+/// Element of a `BigPrimeField`.
+#[derive(Clone)]
+pub struct BigFp {
+    // in 0..p
+    value: BigUint,
+    field: BigPrimeField,
+}
+
+impl BigPrimeField {
+    /// F_p. Panics if p is even or less than 3.
+    pub fn new(p: BigUint) -> BigPrimeField {
+        assert!(
+            p.bit(0) && p > BigUint::one(),
+            "the modulus of BigPrimeField must be an odd prime"
+        );
+        BigPrimeField {
+            modulus: Arc::new(p),
+        }
+    }
+
+    /// The prime p.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Element x mod p.
+    pub fn elem(&self, x: BigUint) -> BigFp {
+        BigFp {
+            value: &x % &self.modulus,
+            field: self.clone(),
+        }
+    }
+
+    /// 0 of the field.
+    pub fn zero(&self) -> BigFp {
+        self.elem(BigUint::zero())
+    }
+
+    /// 1 of the field.
+    pub fn one(&self) -> BigFp {
+        self.elem(BigUint::one())
+    }
+}
+
+impl PartialEq for BigPrimeField {
+    fn eq(&self, other: &BigPrimeField) -> bool {
+        Arc::ptr_eq(&self.modulus, &other.modulus) || self.modulus == other.modulus
+    }
+}
+
+impl Eq for BigPrimeField {}
+
+impl fmt::Debug for BigPrimeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BigPrimeField({})", self.modulus)
+    }
+}
+
+impl BigFp {
+    /// Representative in 0..p.
+    pub fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    /// The field that the element belongs to.
+    pub fn field(&self) -> &BigPrimeField {
+        &self.field
+    }
+
+    fn with_value(&self, value: BigUint) -> BigFp {
+        BigFp {
+            value,
+            field: self.field.clone(),
+        }
+    }
+
+    fn assert_same(&self, other: &BigFp) {
+        assert!(
+            self.field == other.field,
+            "elements of different fields: {:?} and {:?}",
+            self.field,
+            other.field
+        );
+    }
+}
+
+impl PartialEq for BigFp {
+    fn eq(&self, other: &BigFp) -> bool {
+        self.field == other.field && self.value == other.value
+    }
+}
+
+impl Eq for BigFp {}
+
+impl fmt::Debug for BigFp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl fmt::Display for BigFp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl ops::Add for BigFp {
+    type Output = BigFp;
+    fn add(self, other: BigFp) -> BigFp {
+        self.assert_same(&other);
+        let sum = &self.value + &other.value;
+        if sum >= *self.field.modulus {
+            self.with_value(&sum - &self.field.modulus)
+        } else {
+            self.with_value(sum)
+        }
+    }
+}
+
+impl ops::Sub for BigFp {
+    type Output = BigFp;
+    fn sub(self, other: BigFp) -> BigFp {
+        self.assert_same(&other);
+        if self.value >= other.value {
+            self.with_value(&self.value - &other.value)
+        } else {
+            self.with_value(&(&self.value + &self.field.modulus) - &other.value)
+        }
+    }
+}
+
+impl ops::Mul for BigFp {
+    type Output = BigFp;
+    fn mul(self, other: BigFp) -> BigFp {
+        self.assert_same(&other);
+        self.with_value(&(&self.value * &other.value) % &self.field.modulus)
+    }
+}
+
+impl ops::Div for BigFp {
+    type Output = BigFp;
+    // As with `Fp`, dividing by 0 panics.
+    fn div(self, other: BigFp) -> BigFp {
+        self.assert_same(&other);
+        assert!(!other.is_zero(), "division by zero");
+        let inv = other.inv();
+        self.with_value(&(&self.value * &inv.value) % &self.field.modulus)
+    }
+}
+
+impl ops::Neg for BigFp {
+    type Output = BigFp;
+    fn neg(self) -> BigFp {
+        if self.value.is_zero() {
+            return self;
+        }
+        self.with_value(&*self.field.modulus - &self.value)
+    }
+}
+
+impl Field for BigFp {
+    fn zero(&self) -> BigFp {
+        self.with_value(BigUint::zero())
+    }
+    fn one(&self) -> BigFp {
+        self.with_value(BigUint::one())
+    }
+    fn inv(&self) -> BigFp {
+        assert!(!self.is_zero(), "0 has no inverse");
+        let inv = self
+            .value
+            .modinv(&self.field.modulus)
+            .expect("modulus must be a prime");
+        self.with_value(inv)
+    }
+    /// Panics if p does not fit in u64, see `characteristic_big`.
+    fn characteristic(&self) -> u64 {
+        match self.field.modulus.limbs() {
+            [p] => *p,
+            _ => panic!("characteristic does not fit in u64"),
+        }
+    }
+    /// Panics if p does not fit in u128, see `order_big`.
+    fn order(&self) -> u128 {
+        match self.field.modulus.limbs() {
+            [p] => *p as u128,
+            [low, high] => (*high as u128) << 64 | *low as u128,
+            _ => panic!("order does not fit in u128"),
+        }
+    }
+    fn characteristic_big(&self) -> Vec<u64> {
+        self.field.modulus.limbs().to_vec()
+    }
+    fn order_big(&self) -> Vec<u64> {
+        self.field.modulus.limbs().to_vec()
+    }
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+    fn is_one(&self) -> bool {
+        self.value == BigUint::one()
+    }
+    fn pow_big(&self, e: &[u64]) -> BigFp {
+        let e = BigUint::from_limbs(e);
+        self.with_value(self.value.modpow(&e, &self.field.modulus))
+    }
+    fn frobenius(&self) -> BigFp {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::P25519;
+    use crate::{Matrix, MontFp, MontModulus, Polynomial};
+
+    #[test]
+    fn matches_montgomery_field() {
+        type F = MontFp<P25519, 4>;
+        let field = BigPrimeField::new(BigUint::from_limbs(&P25519::MODULUS));
+        let big = |x: F| field.elem(BigUint::from_limbs(&x.to_limbs()));
+
+        let (mut x, y) = (F::new(3), F::from_limbs([1, 2, 3, 4]));
+        for _ in 0..20 {
+            assert_eq!(big(x) + big(y), big(x + y));
+            assert_eq!(big(x) - big(y), big(x - y));
+            assert_eq!(big(x) * big(y), big(x * y));
+            assert_eq!(big(x) / big(y), big(x / y));
+            assert_eq!(-big(x), big(-x));
+            x = x * x + y;
+        }
+    }
+
+    #[test]
+    fn polynomial_and_matrix() {
+        // 2^607 - 1
+        let p = &(&BigUint::one() << 607) - &BigUint::one();
+        let field = BigPrimeField::new(p);
+        let c = |x: u64| field.elem(BigUint::from(x));
+        let poly = |v: &[u64]| Polynomial {
+            coef: v.iter().map(|&x| c(x)).collect(),
+        };
+        // (x + 2)(x + 3) = x^2 + 5x + 6
+        assert_eq!(poly(&[6, 5, 1]) / poly(&[2, 1]), poly(&[3, 1]));
+        let f = poly(&[1, 2, 3]);
+        assert_eq!(f.pow_mod(5, &poly(&[1, 0, 1])), f.pow(5) % poly(&[1, 0, 1]));
+
+        let m = Matrix {
+            element: vec![vec![c(2), c(4), c(1)], vec![c(3), c(5), c(1)]],
+        };
+        let reduced = m.sweep_method();
+        assert_eq!(reduced.element[0][..2], [c(1), c(0)]);
+        assert_eq!(reduced.element[1][..2], [c(0), c(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different fields")]
+    fn mixing_fields() {
+        let a = BigPrimeField::new(BigUint::from(7u64)).one();
+        let b = BigPrimeField::new(BigUint::from(11u64)).one();
+        let _ = a + b;
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let field = BigPrimeField::new(BigUint::from(7u64));
+        let _ = field.one() / field.zero();
+    }
+}
@@ -0,0 +1,616 @@
+//! Arbitrary-precision unsigned integers.
+
+use crate::integer::jacobi;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops;
+
+/// Unsigned integer of any size, stored as little-endian 64-bit limbs.
+/// ```
+/// use galois_field::*;
+/// let a = BigUint::from_str_radix("340282366920938463463374607431768211457", 10).unwrap(); // 2^128 + 1
+/// let b = BigUint::from(3u64);
+/// assert_eq!((&a * &b).to_string(), "1020847100762815390390123822295304634371");
+/// assert_eq!(a.divrem(&b), (BigUint::from(113427455640312821154458202477256070485u128), BigUint::from(2u64)));
+/// assert_eq!(b.modinv(&a).map(|x| &(&x * &b) % &a), Some(BigUint::one()));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    // no trailing zero limbs, so 0 is empty
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    /// 0.
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    /// 1.
+    pub fn one() -> BigUint {
+        BigUint { limbs: vec![1] }
+    }
+
+    /// Integer with the given little-endian limbs.
+    pub fn from_limbs(limbs: &[u64]) -> BigUint {
+        let mut limbs = limbs.to_vec();
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    /// Little-endian limbs, empty for 0.
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    /// Parse digits in the given radix (2..=36), e.g. `from_str_radix("ff", 16)`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Option<BigUint> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        if s.is_empty() {
+            return None;
+        }
+        let mut result = BigUint::zero();
+        for c in s.chars() {
+            let d = c.to_digit(radix)?;
+            result = result.mul_small(radix as u64).add_small(d as u64);
+        }
+        Some(result)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of bits, 0 for 0.
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 64 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Bit i, counting from the least significant.
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 64)
+            .is_some_and(|l| l >> (i % 64) & 1 == 1)
+    }
+
+    /// Quotient and remainder, by Knuth's algorithm D. Panics if `other` is 0.
+    pub fn divrem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        assert!(!other.is_zero(), "division by 0");
+        if self < other {
+            return (BigUint::zero(), self.clone());
+        }
+        if other.limbs.len() == 1 {
+            let (q, r) = self.divrem_small(other.limbs[0]);
+            return (q, BigUint::from(r));
+        }
+
+        // normalize so that the top limb of v has its top bit set
+        let s = other.limbs.last().unwrap().leading_zeros() as usize;
+        let v = (other << s).limbs;
+        let mut u = (self << s).limbs;
+        u.resize(self.limbs.len() + 1, 0);
+        let n = v.len();
+        let m = u.len() - n;
+        let mut q = vec![0u64; m];
+
+        for j in (0..m).rev() {
+            // estimate the quotient digit from the top two limbs, then correct it
+            let num = (u[j + n] as u128) << 64 | u[j + n - 1] as u128;
+            let mut qhat = num / v[n - 1] as u128;
+            let mut rhat = num % v[n - 1] as u128;
+            while qhat >> 64 != 0 || qhat * v[n - 2] as u128 > (rhat << 64 | u[j + n - 2] as u128) {
+                qhat -= 1;
+                rhat += v[n - 1] as u128;
+                if rhat >> 64 != 0 {
+                    break;
+                }
+            }
+
+            // u[j..=j+n] -= qhat * v
+            let mut k: i128 = 0;
+            for i in 0..n {
+                let p = qhat * v[i] as u128;
+                let t = u[i + j] as i128 - k - (p as u64) as i128;
+                u[i + j] = t as u64;
+                k = (p >> 64) as i128 - (t >> 64);
+            }
+            let t = u[j + n] as i128 - k;
+            u[j + n] = t as u64;
+
+            q[j] = qhat as u64;
+            if t < 0 {
+                // qhat was one too large, add v back
+                q[j] -= 1;
+                let mut carry = 0u128;
+                for i in 0..n {
+                    let t = u[i + j] as u128 + v[i] as u128 + carry;
+                    u[i + j] = t as u64;
+                    carry = t >> 64;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u64);
+            }
+        }
+        u.truncate(n);
+        (BigUint::from_limbs(&q), &BigUint::from_limbs(&u) >> s)
+    }
+
+    /// self^e mod m by square-and-multiply.
+    pub fn modpow(&self, e: &BigUint, m: &BigUint) -> BigUint {
+        let base = self % m;
+        let mut result = &BigUint::one() % m;
+        for i in (0..e.bits()).rev() {
+            result = &(&result * &result) % m;
+            if e.bit(i) {
+                result = &(&result * &base) % m;
+            }
+        }
+        result
+    }
+
+    /// Inverse of self mod m, or None if they are not coprime.
+    pub fn modinv(&self, m: &BigUint) -> Option<BigUint> {
+        // r_i = t_i * self (mod m)
+        let (mut r0, mut r1) = (m.clone(), self % m);
+        let (mut t0, mut t1) = (BigUint::zero(), BigUint::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.divrem(&r1);
+            let t = &(&(&t0 + m) - &(&(&q * &t1) % m)) % m;
+            (r0, r1) = (r1, r);
+            (t0, t1) = (t1, t);
+        }
+        if r0 == BigUint::one() {
+            Some(t0)
+        } else {
+            None
+        }
+    }
+
+    /// Greatest common divisor.
+    pub fn gcd(&self, other: &BigUint) -> BigUint {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            (a, b) = (b, r);
+        }
+        a
+    }
+
+    /// The Baillie-PSW test, as in `is_prime`: trial division by the primes up to 37, then
+    /// Miller-Rabin to base 2 and a strong Lucas test.
+    ///
+    /// It is always true for primes and exact below 2^64. Above that no composite passing it is
+    /// known, but none has been proven not to exist.
+    pub fn is_probable_prime(&self) -> bool {
+        if *self < BigUint::from(2u64) {
+            return false;
+        }
+        for a in [2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            if *self == BigUint::from(a) {
+                return true;
+            }
+            if self.divrem_small(a).1 == 0 {
+                return false;
+            }
+        }
+        self.is_strong_probable_prime(&BigUint::from(2u64)) && self.is_strong_lucas_probable_prime()
+    }
+
+    // Miller-Rabin round: n - 1 = d 2^s with d odd, and a^d = 1 or a^(d 2^r) = -1 for some r < s
+    fn is_strong_probable_prime(&self, a: &BigUint) -> bool {
+        let one = BigUint::one();
+        let minus_one = self - &one;
+        let s = (0..).find(|&i| minus_one.bit(i)).unwrap();
+        let d = &minus_one >> s;
+        let mut x = a.modpow(&d, self);
+        if x == one || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = &(&x * &x) % self;
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    }
+
+    // Strong Lucas test for odd n > 37 with Selfridge's parameters, as in
+    // integer::is_strong_lucas_probable_prime
+    fn is_strong_lucas_probable_prime(&self) -> bool {
+        let n = self;
+        // no such D exists for a square
+        let root = n.isqrt();
+        if &root * &root == *n {
+            return false;
+        }
+        // (D/n) from (n mod |D| / |D|) by reciprocity, with (-1/n) = -1 for n = 3 mod 4
+        let n_mod_4 = n.limbs[0] % 4;
+        let mut abs = 5u64;
+        let mut negative = false;
+        loop {
+            let mut j = jacobi(n.divrem_small(abs).1 as u128, abs as u128);
+            if n_mod_4 == 3 && (abs % 4 == 3) != negative {
+                j = -j;
+            }
+            match j {
+                -1 => break,
+                0 => return *n == BigUint::from(abs),
+                _ => {}
+            }
+            abs += 2;
+            negative = !negative;
+        }
+        // D and Q = (1 - D)/4 as residues mod n
+        let (d, q) = if negative {
+            (n - &BigUint::from(abs), BigUint::from(abs.div_ceil(4)))
+        } else {
+            (BigUint::from(abs), n - &BigUint::from(abs / 4))
+        };
+        let add = |a: &BigUint, b: &BigUint| &(a + b) % n;
+        let sub = |a: &BigUint, b: &BigUint| &(&(a + n) - b) % n;
+        let mul = |a: &BigUint, b: &BigUint| &(a * b) % n;
+        let half = |x: BigUint| {
+            if x.bit(0) {
+                &(&x + n) >> 1
+            } else {
+                &x >> 1
+            }
+        };
+
+        let n_plus_one = n + &BigUint::one();
+        let s = (0..).find(|&i| n_plus_one.bit(i)).unwrap();
+        let e = &n_plus_one >> s;
+        // U_k, V_k and Q^k for k the leading bits of e, starting from k = 1 with P = 1
+        let (mut u, mut v, mut qk) = (BigUint::one(), BigUint::one(), q.clone());
+        for i in (0..e.bits() - 1).rev() {
+            // k -> 2k
+            u = mul(&u, &v);
+            v = sub(&mul(&v, &v), &add(&qk, &qk));
+            qk = mul(&qk, &qk);
+            if e.bit(i) {
+                // k -> k + 1
+                (u, v) = (half(add(&u, &v)), half(add(&mul(&d, &u), &v)));
+                qk = mul(&qk, &q);
+            }
+        }
+        if u.is_zero() || v.is_zero() {
+            return true;
+        }
+        for _ in 1..s {
+            v = sub(&mul(&v, &v), &add(&qk, &qk));
+            qk = mul(&qk, &qk);
+            if v.is_zero() {
+                return true;
+            }
+        }
+        false
+    }
+
+    // floor(sqrt(self)) by Newton's method, starting above the root
+    fn isqrt(&self) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+        let mut x = &BigUint::one() << self.bits().div_ceil(2);
+        loop {
+            let y = &(&x + &(self / &x)) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    fn add_small(&self, x: u64) -> BigUint {
+        self + &BigUint::from(x)
+    }
+
+    fn mul_small(&self, x: u64) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u128;
+        for &l in &self.limbs {
+            let t = l as u128 * x as u128 + carry;
+            limbs.push(t as u64);
+            carry = t >> 64;
+        }
+        limbs.push(carry as u64);
+        BigUint::from_limbs(&limbs)
+    }
+
+    fn divrem_small(&self, x: u64) -> (BigUint, u64) {
+        let mut q = vec![0; self.limbs.len()];
+        let mut r = 0u128;
+        for i in (0..self.limbs.len()).rev() {
+            let num = r << 64 | self.limbs[i] as u128;
+            q[i] = (num / x as u128) as u64;
+            r = num % x as u128;
+        }
+        (BigUint::from_limbs(&q), r as u64)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(x: u64) -> BigUint {
+        BigUint::from_limbs(&[x])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(x: u128) -> BigUint {
+        BigUint::from_limbs(&[x as u64, (x >> 64) as u64])
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // base 10^19 digits, least significant first
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut x = self.clone();
+        while !x.is_zero() {
+            let (q, r) = x.divrem_small(CHUNK);
+            chunks.push(r);
+            x = q;
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((top, rest)) => {
+                write!(f, "{}", top)?;
+                for c in rest.iter().rev() {
+                    write!(f, "{:019}", c)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((top, rest)) => {
+                write!(f, "{:x}", top)?;
+                for l in rest.iter().rev() {
+                    write!(f, "{:016x}", l)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ops::Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (&self.limbs, &other.limbs)
+        } else {
+            (&other.limbs, &self.limbs)
+        };
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u128;
+        for (i, &l) in long.iter().enumerate() {
+            let t = l as u128 + short.get(i).copied().unwrap_or(0) as u128 + carry;
+            limbs.push(t as u64);
+            carry = t >> 64;
+        }
+        limbs.push(carry as u64);
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+impl ops::Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+    /// Panics if `other` is larger than `self`.
+    fn sub(self, other: &BigUint) -> BigUint {
+        assert!(self >= other, "subtraction overflow");
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = false;
+        for (i, &l) in self.limbs.iter().enumerate() {
+            let (d, b1) = l.overflowing_sub(other.limbs.get(i).copied().unwrap_or(0));
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            limbs.push(d);
+            borrow = b1 || b2;
+        }
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+impl ops::Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = limbs[i + j] as u128 + a as u128 * b as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+impl ops::Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn div(self, other: &BigUint) -> BigUint {
+        self.divrem(other).0
+    }
+}
+
+impl ops::Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.divrem(other).1
+    }
+}
+
+// the same operations on owned values
+macro_rules! forward_owned {
+    ($($trait:ident $method:ident),*) => {$(
+        impl ops::$trait for BigUint {
+            type Output = BigUint;
+            fn $method(self, other: BigUint) -> BigUint {
+                ops::$trait::$method(&self, &other)
+            }
+        }
+    )*};
+}
+
+forward_owned!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+impl ops::Shl<usize> for &BigUint {
+    type Output = BigUint;
+    fn shl(self, s: usize) -> BigUint {
+        let (words, bits) = (s / 64, s % 64);
+        let mut limbs = vec![0u64; words];
+        let mut carry = 0u64;
+        for &l in &self.limbs {
+            limbs.push(l << bits | carry);
+            carry = if bits == 0 { 0 } else { l >> (64 - bits) };
+        }
+        limbs.push(carry);
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+impl ops::Shr<usize> for &BigUint {
+    type Output = BigUint;
+    fn shr(self, s: usize) -> BigUint {
+        let (words, bits) = (s / 64, s % 64);
+        let limbs: Vec<u64> = (words..self.limbs.len())
+            .map(|i| {
+                let high = match self.limbs.get(i + 1) {
+                    Some(&h) if bits != 0 => h << (64 - bits),
+                    _ => 0,
+                };
+                self.limbs[i] >> bits | high
+            })
+            .collect();
+        BigUint::from_limbs(&limbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integer::is_prime;
+
+    fn big(s: &str) -> BigUint {
+        BigUint::from_str_radix(s, 10).unwrap()
+    }
+
+    #[test]
+    fn divrem_identity() {
+        // values with limbs near 0 and 2^64, which exercise the quotient correction
+        let values = [
+            big("0"),
+            big("1"),
+            big("18446744073709551615"),
+            big("18446744073709551616"),
+            BigUint::from_limbs(&[0, 0, 1 << 63]),
+            BigUint::from_limbs(&[!0, !0, !0, !0]),
+            BigUint::from_limbs(&[1, 0, 0, 0x8000_0000_0000_0001]),
+            BigUint::from_limbs(&[!0, 0x7fff_ffff_ffff_ffff, !0, 1, 12345]),
+            big("6864797660130609714981900799081393217269435300143305409394463459185543183397656052122559640661454554977296311391480858037121987999716643812574028291115057151"),
+        ];
+        for u in &values {
+            for v in values.iter().filter(|v| !v.is_zero()) {
+                let (q, r) = u.divrem(v);
+                assert!(r < *v);
+                assert_eq!(&(&q * v) + &r, *u);
+            }
+        }
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        // 2^521 - 1
+        let s = "6864797660130609714981900799081393217269435300143305409394463459185543183397656052122559640661454554977296311391480858037121987999716643812574028291115057151";
+        let p = big(s);
+        assert_eq!(p.to_string(), s);
+        assert_eq!(p.bits(), 521);
+        assert_eq!(&(&p + &BigUint::one()) >> 521, BigUint::one());
+        assert_eq!(format!("{:x}", BigUint::from(255u64)), "ff");
+        assert_eq!(BigUint::from_str_radix("12a", 10), None);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let p = &(&BigUint::one() << 521) - &BigUint::one();
+        assert!(p.is_probable_prime());
+        assert!(!(&p * &p).is_probable_prime());
+        let a = big("123456789012345678901234567890123456789");
+        // Fermat
+        assert_eq!(a.modpow(&(&p - &BigUint::one()), &p), BigUint::one());
+        let inv = a.modinv(&p).unwrap();
+        assert_eq!(&(&a * &inv) % &p, BigUint::one());
+        assert_eq!(big("12").gcd(&big("18")), big("6"));
+        assert_eq!(big("12").modinv(&big("18")), None);
+    }
+
+    #[test]
+    fn baillie_psw() {
+        for n in 0..3000u64 {
+            assert_eq!(
+                BigUint::from(n).is_probable_prime(),
+                is_prime(n as u128),
+                "{}",
+                n
+            );
+        }
+        // a strong pseudoprime to the first 12 prime bases, which Miller-Rabin with them accepts
+        let psi12 = big("318665857834031151167461");
+        assert!(psi12.is_strong_probable_prime(&BigUint::from(37u64)));
+        assert!(!psi12.is_probable_prime());
+        assert!(!big("3317044064679887385961981").is_probable_prime());
+        // strong Lucas pseudoprimes, which Miller-Rabin to base 2 rejects
+        for n in [
+            5459u64, 5777, 10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519,
+        ] {
+            assert!(BigUint::from(n).is_strong_lucas_probable_prime());
+            assert!(!BigUint::from(n).is_probable_prime());
+        }
+        // 2^127 - 1 and (2^89 - 1)(2^127 - 1)
+        let m127 = &(&BigUint::one() << 127) - &BigUint::one();
+        assert!(m127.is_probable_prime());
+        assert!(!(&m127 * &(&(&BigUint::one() << 89) - &BigUint::one())).is_probable_prime());
+        assert_eq!(
+            big("1000000000000000000000000").isqrt(),
+            big("1000000000000")
+        );
+        assert_eq!(big("999999999999999999999999").isqrt(), big("999999999999"));
+    }
+}
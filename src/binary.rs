@@ -0,0 +1,558 @@
+//! Binary fields GF(2^n) with coefficients packed into 64-bit words.
+
+use crate::integer::factorize;
+use crate::Field;
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+/// GF(2^n) = F_2[x] / (f) for a sparse irreducible f, created once and used to construct elements.
+///
+/// An element is a polynomial of degree below n whose coefficient of x^i is bit i of the words.
+/// Addition is XOR, multiplication is carry-less (PCLMULQDQ on x86_64 when the CPU has it)
+/// followed by reduction with the few nonzero terms of f.
+/// ```
+/// use galois_field::*;
+/// // x^128 + x^7 + x^2 + x + 1, the modulus of GCM
+/// let field = BinaryField::with_exponents(&[128, 7, 2, 1, 0]);
+/// let a = field.elem(&[0x0123456789abcdef, 0xfedcba9876543210]);
+/// assert_eq!(a.clone() + a.clone(), field.zero());
+/// assert_eq!(a.clone() * a.inv(), field.one());
+/// // x^127 * x = x^7 + x^2 + x + 1
+/// let x127 = field.elem(&[0, 1 << 63]);
+/// assert_eq!(x127 * field.gen(), field.elem(&[0x87]));
+/// ```
+#[derive(Clone)]
+pub struct BinaryField {
+    inner: Arc<BinaryFieldInner>,
+}
+
+struct BinaryFieldInner {
+    degree: usize,
+    // exponents of the nonzero terms of the modulus below x^n, descending
+    low: Vec<usize>,
+}
+
+/// Element of a `BinaryField`.
+#[derive(Clone)]
+pub struct GF2n {
+    // exactly ceil(n / 64) words, bits at and above n are 0
+    words: Vec<u64>,
+    field: BinaryField,
+}
+
+impl BinaryField {
+    /// GF(2^n) defined by the irreducible trinomial, or else pentanomial, of degree n
+    /// in the order of `GF::low_weight_irreducible`.
+    ///
+    /// Panics if n = 0 or there is no such polynomial.
+    /// ```
+    /// use galois_field::*;
+    /// assert_eq!(BinaryField::new(8).exponents(), vec![8, 4, 3, 1, 0]);
+    /// assert_eq!(BinaryField::new(233).exponents(), vec![233, 74, 0]);
+    /// ```
+    pub fn new(n: usize) -> BinaryField {
+        assert!(n >= 1, "degree of the extension must be at least 1");
+        if n == 1 {
+            return BinaryField::from_low(1, vec![0]);
+        }
+        let trinomials = (1..n).map(|k| vec![k, 0]);
+        let pentanomials =
+            (3..n).flat_map(|c| (2..c).flat_map(move |b| (1..b).map(move |a| vec![c, b, a, 0])));
+        trinomials
+            .chain(pentanomials)
+            .map(|low| BinaryField::from_low(n, low))
+            .find(|field| field.is_irreducible())
+            .expect("no irreducible trinomial or pentanomial of this degree")
+    }
+
+    /// GF(2^n) defined by the polynomial whose nonzero terms have the given exponents,
+    /// the largest being n.
+    ///
+    /// Panics if the polynomial is not irreducible.
+    pub fn with_exponents(exponents: &[usize]) -> BinaryField {
+        let mut exponents = exponents.to_vec();
+        exponents.sort_unstable_by(|a, b| b.cmp(a));
+        exponents.dedup();
+        assert!(
+            exponents.len() >= 2 && exponents[0] >= 1,
+            "modulus must not be a constant"
+        );
+        let field = BinaryField::from_low(exponents[0], exponents[1..].to_vec());
+        assert!(field.is_irreducible(), "modulus must be irreducible");
+        field
+    }
+
+    fn from_low(degree: usize, low: Vec<usize>) -> BinaryField {
+        BinaryField {
+            inner: Arc::new(BinaryFieldInner { degree, low }),
+        }
+    }
+
+    /// Degree n of the extension over F_2.
+    pub fn degree(&self) -> usize {
+        self.inner.degree
+    }
+
+    /// Exponents of the nonzero terms of the modulus, descending.
+    pub fn exponents(&self) -> Vec<usize> {
+        std::iter::once(self.inner.degree)
+            .chain(self.inner.low.iter().copied())
+            .collect()
+    }
+
+    /// Element with bit i of `words` as the coefficient of x^i, reduced modulo the modulus.
+    pub fn elem(&self, words: &[u64]) -> GF2n {
+        GF2n {
+            words: self.reduce(words.to_vec()),
+            field: self.clone(),
+        }
+    }
+
+    /// 0 of the field.
+    pub fn zero(&self) -> GF2n {
+        self.elem(&[])
+    }
+
+    /// 1 of the field.
+    pub fn one(&self) -> GF2n {
+        self.elem(&[1])
+    }
+
+    /// The class of x, a root of the modulus.
+    pub fn gen(&self) -> GF2n {
+        self.elem(&[2])
+    }
+
+    fn words(&self) -> usize {
+        self.inner.degree.div_ceil(64)
+    }
+
+    // c mod f, as exactly ceil(n / 64) words
+    fn reduce(&self, mut c: Vec<u64>) -> Vec<u64> {
+        let n = self.inner.degree;
+        // c = h x^n + l = h (f - x^n) + l, lowering the degree by n - (second exponent) each round
+        while degree(&c).is_some_and(|d| d >= n) {
+            let high = shr(&c, n);
+            truncate(&mut c, n);
+            for &e in &self.inner.low {
+                xor_shl(&mut c, &high, e);
+            }
+        }
+        c.resize(self.words(), 0);
+        c
+    }
+
+    fn mul_words(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        self.reduce(clmul(a, b))
+    }
+
+    // Rabin's test with x^(2^k) computed by squaring in F_2[x] / (f)
+    fn is_irreducible(&self) -> bool {
+        let n = self.inner.degree;
+        let x = self.reduce(vec![2]);
+        let mut powers = vec![x.clone()];
+        for k in 0..n {
+            powers.push(self.mul_words(&powers[k], &powers[k]));
+        }
+        if powers[n] != x {
+            return false;
+        }
+        let modulus = self.exponents().iter().fold(Vec::new(), |mut f, &e| {
+            xor_shl(&mut f, &[1], e);
+            f
+        });
+        factorize(n as u128).iter().all(|&(r, _)| {
+            let mut g = powers[n / r as usize].clone();
+            xor_shl(&mut g, &x, 0);
+            degree(&gcd(modulus.clone(), g)) == Some(0)
+        })
+    }
+}
+
+impl PartialEq for BinaryField {
+    fn eq(&self, other: &BinaryField) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+            || (self.inner.degree == other.inner.degree && self.inner.low == other.inner.low)
+    }
+}
+
+impl Eq for BinaryField {}
+
+impl fmt::Debug for BinaryField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BinaryField{:?}", self.exponents())
+    }
+}
+
+impl GF2n {
+    /// Coefficients packed into ceil(n / 64) little-endian words.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The field that the element belongs to.
+    pub fn field(&self) -> &BinaryField {
+        &self.field
+    }
+
+    fn with_words(&self, words: Vec<u64>) -> GF2n {
+        GF2n {
+            words,
+            field: self.field.clone(),
+        }
+    }
+
+    fn assert_same(&self, other: &GF2n) {
+        assert!(
+            self.field == other.field,
+            "elements of different fields: {:?} and {:?}",
+            self.field,
+            other.field
+        );
+    }
+}
+
+impl PartialEq for GF2n {
+    fn eq(&self, other: &GF2n) -> bool {
+        self.field == other.field && self.words == other.words
+    }
+}
+
+impl Eq for GF2n {}
+
+impl fmt::Debug for GF2n {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x")?;
+        for w in self.words.iter().rev() {
+            write!(f, "{:016x}", w)?;
+        }
+        Ok(())
+    }
+}
+
+impl ops::Add for GF2n {
+    type Output = GF2n;
+    fn add(self, other: GF2n) -> GF2n {
+        self.assert_same(&other);
+        let words = self.words.iter().zip(&other.words).map(|(a, b)| a ^ b);
+        self.with_words(words.collect())
+    }
+}
+
+impl ops::Sub for GF2n {
+    type Output = GF2n;
+    // -b = b in characteristic 2
+    fn sub(self, other: GF2n) -> GF2n {
+        self.assert_same(&other);
+        let words = self.words.iter().zip(&other.words).map(|(a, b)| a ^ b);
+        self.with_words(words.collect())
+    }
+}
+
+impl ops::Mul for GF2n {
+    type Output = GF2n;
+    fn mul(self, other: GF2n) -> GF2n {
+        self.assert_same(&other);
+        self.with_words(self.field.mul_words(&self.words, &other.words))
+    }
+}
+
+impl ops::Div for GF2n {
+    type Output = GF2n;
+    // As with `Fp`, dividing by 0 panics.
+    fn div(self, other: GF2n) -> GF2n {
+        self.assert_same(&other);
+        assert!(!other.is_zero(), "division by zero");
+        let inv = other.inv();
+        self.with_words(self.field.mul_words(&self.words, &inv.words))
+    }
+}
+
+impl ops::Neg for GF2n {
+    type Output = GF2n;
+    fn neg(self) -> GF2n {
+        self
+    }
+}
+
+impl Field for GF2n {
+    fn zero(&self) -> GF2n {
+        self.field.zero()
+    }
+    fn one(&self) -> GF2n {
+        self.field.one()
+    }
+    /// a^(2^n - 2).
+    fn inv(&self) -> GF2n {
+        assert!(!self.is_zero(), "0 has no inverse");
+        let n = self.field.degree();
+        let mut e = vec![u64::MAX; n.div_ceil(64)];
+        truncate(&mut e, n);
+        e[0] ^= 1;
+        self.pow_big(&e)
+    }
+    fn characteristic(&self) -> u64 {
+        2
+    }
+    /// Panics if n >= 128, see `order_big`.
+    fn order(&self) -> u128 {
+        let n = self.field.degree();
+        assert!(n < 128, "order does not fit in u128");
+        1 << n
+    }
+    fn order_big(&self) -> Vec<u64> {
+        let mut order = Vec::new();
+        xor_shl(&mut order, &[1], self.field.degree());
+        order
+    }
+    fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+    fn is_one(&self) -> bool {
+        self.words[0] == 1 && self.words[1..].iter().all(|&w| w == 0)
+    }
+    fn frobenius(&self) -> GF2n {
+        self.with_words(self.field.mul_words(&self.words, &self.words))
+    }
+}
+
+// polynomials over F_2 as little-endian words, possibly with zero words on top
+
+fn degree(a: &[u64]) -> Option<usize> {
+    let i = a.iter().rposition(|&w| w != 0)?;
+    Some(64 * i + 63 - a[i].leading_zeros() as usize)
+}
+
+// a >> s, i.e. the quotient by x^s
+fn shr(a: &[u64], s: usize) -> Vec<u64> {
+    let (words, bits) = (s / 64, s % 64);
+    let mut result: Vec<u64> = a.iter().skip(words).copied().collect();
+    if bits > 0 {
+        for i in 0..result.len() {
+            let carry = result.get(i + 1).map_or(0, |&w| w << (64 - bits));
+            result[i] = result[i] >> bits | carry;
+        }
+    }
+    result
+}
+
+// a mod x^s
+fn truncate(a: &mut Vec<u64>, s: usize) {
+    a.truncate(s.div_ceil(64));
+    if !s.is_multiple_of(64) {
+        if let Some(last) = a.get_mut(s / 64) {
+            *last &= (1 << (s % 64)) - 1;
+        }
+    }
+}
+
+// a += b x^s
+fn xor_shl(a: &mut Vec<u64>, b: &[u64], s: usize) {
+    let (words, bits) = (s / 64, s % 64);
+    let len = b.len() + words + 1;
+    if a.len() < len {
+        a.resize(len, 0);
+    }
+    for (i, &w) in b.iter().enumerate() {
+        a[i + words] ^= w << bits;
+        if bits > 0 {
+            a[i + words + 1] ^= w >> (64 - bits);
+        }
+    }
+}
+
+fn gcd(mut a: Vec<u64>, mut b: Vec<u64>) -> Vec<u64> {
+    while let Some(db) = degree(&b) {
+        // a mod b
+        while let Some(da) = degree(&a).filter(|&da| da >= db) {
+            let shifted = b.clone();
+            xor_shl(&mut a, &shifted, da - db);
+        }
+        std::mem::swap(&mut a, &mut b);
+    }
+    a
+}
+
+// product in F_2[x], schoolbook over words
+fn clmul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        for (j, &y) in b.iter().enumerate() {
+            let (low, high) = clmul64(x, y);
+            result[i + j] ^= low;
+            result[i + j + 1] ^= high;
+        }
+    }
+    result
+}
+
+// (low, high) words of the 127-bit carry-less product
+fn clmul64(a: u64, b: u64) -> (u64, u64) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("pclmulqdq") {
+            // SAFETY: the CPU supports PCLMULQDQ, checked above
+            return unsafe { clmul64_pclmulqdq(a, b) };
+        }
+    }
+    clmul64_portable(a, b)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn clmul64_pclmulqdq(a: u64, b: u64) -> (u64, u64) {
+    use std::arch::x86_64::*;
+    let product = _mm_clmulepi64_si128(_mm_cvtsi64_si128(a as i64), _mm_cvtsi64_si128(b as i64), 0);
+    (
+        _mm_cvtsi128_si64(product) as u64,
+        _mm_cvtsi128_si64(_mm_srli_si128(product, 8)) as u64,
+    )
+}
+
+fn clmul64_portable(a: u64, b: u64) -> (u64, u64) {
+    let (mut low, mut high) = (0u64, 0u64);
+    for i in 0..64 {
+        if b >> i & 1 == 1 {
+            low ^= a << i;
+            if i > 0 {
+                high ^= a >> (64 - i);
+            }
+        }
+    }
+    (low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FiniteField, Polynomial, GF};
+
+    // GF(2^n) over the same modulus, and the conversions from packed words
+    fn reference(field: &BinaryField) -> (GF, impl Fn(&GF2n) -> FiniteField) {
+        let gf2 = GF::new(2, 1);
+        let mut coef = vec![gf2.zero(); field.degree() + 1];
+        for e in field.exponents() {
+            coef[e] = gf2.one();
+        }
+        let gf = GF::with_modulus(Polynomial { coef });
+        let n = field.degree();
+        let to_gf = {
+            let gf = gf.clone();
+            move |a: &GF2n| {
+                let bits: Vec<i64> = (0..n)
+                    .map(|i| (a.words()[i / 64] >> (i % 64) & 1) as i64)
+                    .collect();
+                gf.elem(&bits)
+            }
+        };
+        (gf, to_gf)
+    }
+
+    // xorshift, enough to spread bits over all words
+    fn sample(field: &BinaryField, seed: &mut u64) -> GF2n {
+        let words: Vec<u64> = (0..field.degree().div_ceil(64))
+            .map(|_| {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 7;
+                *seed ^= *seed << 17;
+                *seed
+            })
+            .collect();
+        field.elem(&words)
+    }
+
+    #[test]
+    fn matches_polynomial_basis() {
+        let mut seed = 0x9e3779b97f4a7c15;
+        for field in [
+            BinaryField::new(8),
+            BinaryField::new(64),
+            BinaryField::new(113),
+            BinaryField::with_exponents(&[127, 1, 0]),
+        ] {
+            let (_, to_gf) = reference(&field);
+            for _ in 0..10 {
+                let (a, b) = (sample(&field, &mut seed), sample(&field, &mut seed));
+                assert_eq!(to_gf(&(a.clone() + b.clone())), to_gf(&a) + to_gf(&b));
+                assert_eq!(to_gf(&(a.clone() * b.clone())), to_gf(&a) * to_gf(&b));
+                assert_eq!(to_gf(&(a.clone() / b.clone())), to_gf(&a) / to_gf(&b));
+                assert_eq!(to_gf(&a.frobenius()), to_gf(&a).pow(2));
+            }
+        }
+    }
+
+    #[test]
+    fn default_moduli() {
+        let gf2 = GF::new(2, 1);
+        for n in 1..=20 {
+            let f = gf2.low_weight_irreducible(n).unwrap();
+            let exponents: Vec<usize> = (0..=n).rev().filter(|&i| f.coef[i].is_one()).collect();
+            assert_eq!(BinaryField::new(n).exponents(), exponents);
+        }
+        // NIST B-163
+        assert_eq!(BinaryField::new(163).exponents(), vec![163, 7, 6, 3, 0]);
+    }
+
+    #[test]
+    fn large_degrees() {
+        let mut seed = 1;
+        // NIST B-283 and B-571
+        for exponents in [&[283, 12, 7, 5, 0], &[571, 10, 5, 2, 0]] {
+            let field = BinaryField::with_exponents(exponents);
+            let n = field.degree();
+            let a = sample(&field, &mut seed);
+            let b = sample(&field, &mut seed);
+            assert_eq!(a.clone() * a.inv(), field.one());
+            assert_eq!(
+                (a.clone() + b.clone()) * b.clone(),
+                a.clone() * b.clone() + b.pow(2)
+            );
+            // a^(2^n) = a
+            let mut c = a.clone();
+            for _ in 0..n {
+                c = c.frobenius();
+            }
+            assert_eq!(c, a);
+            assert_eq!(a.pow_big(&a.order_big()), a);
+        }
+    }
+
+    #[test]
+    fn portable_clmul() {
+        let mut seed = 7u64;
+        for _ in 0..100 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let (a, b) = (seed, seed.rotate_left(29) ^ 0x5555);
+            assert_eq!(clmul64(a, b), clmul64_portable(a, b));
+            // bit by bit
+            let mut expected = 0u128;
+            for i in 0..64 {
+                if b >> i & 1 == 1 {
+                    expected ^= (a as u128) << i;
+                }
+            }
+            assert_eq!(
+                clmul64_portable(a, b),
+                (expected as u64, (expected >> 64) as u64)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "irreducible")]
+    fn reducible_modulus() {
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2
+        BinaryField::with_exponents(&[4, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let field = BinaryField::new(8);
+        let _ = field.one() / field.zero();
+    }
+}
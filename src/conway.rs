@@ -0,0 +1,360 @@
+//! Conway polynomials, the standard choice of modulus in Sage, Magma and GAP.
+
+use crate::integer::{factorize, is_prime_u64};
+use crate::{Element, FiniteField, NumType, Polynomial};
+
+impl Polynomial<FiniteField> {
+    /// Conway polynomial C(p, n), the primitive polynomial of degree n over F_p that is least in
+    /// the Conway order and compatible with C(p, m) for every m dividing n:
+    /// C(p, m)(x^((p^n-1)/(p^m-1))) = 0 mod C(p, n).
+    ///
+    /// Taken from a table for small p^n and otherwise found by searching the candidates in the
+    /// Conway order, which is limited to p^n < 2^32: that takes seconds up to about 2^28 and
+    /// minutes near the limit. Panics if p^n is beyond the table and the limit.
+    /// ```
+    /// use galois_field::*;
+    /// // x^8 + x^4 + x^3 + x^2 + 1
+    /// let f = Polynomial::conway(2, 8);
+    /// let coef = [1, 0, 1, 1, 1, 0, 0, 0, 1];
+    /// assert_eq!(f, Polynomial { coef: coef.iter().map(|&c| GF::new(2, 1).elem(&[c])).collect() });
+    /// // the same GF(2^8) as Sage's GF(2^8)
+    /// let gf = GF::with_modulus(f);
+    /// ```
+    pub fn conway(char: u64, n: usize) -> Polynomial {
+        assert!(is_prime_u64(char), "char must be a prime");
+        assert!(n >= 1, "degree must be at least 1");
+        let poly = |coef: Vec<NumType>| Polynomial {
+            coef: coef
+                .into_iter()
+                .map(|c| FiniteField {
+                    char,
+                    element: Element::PrimeField { element: c },
+                })
+                .collect(),
+        };
+        match CONWAY.binary_search_by_key(&(char, n), |&(p, m, _)| (p, m)) {
+            Ok(i) => poly(CONWAY[i].2.iter().map(|&c| c as NumType).collect()),
+            Err(_) => compute_conway(char, n),
+        }
+    }
+}
+
+// first polynomial in the Conway order which is primitive and compatible with its subfields
+fn compute_conway(char: u64, n: usize) -> Polynomial {
+    let p = char as u128;
+    let q = p.checked_pow(n as u32);
+    assert!(
+        q.is_some_and(|q| q < 1 << 32),
+        "C({}, {}) is not in the table and p^n is not below 2^32",
+        char,
+        n
+    );
+    let order = q.unwrap() - 1;
+    // factored once for the primitivity test of every candidate
+    let factors = factorize(order);
+    // compatibility is transitive, so the maximal subfields GF(p^(n/r)) for primes r | n suffice;
+    // F_p is handled through a_0 below
+    let subfields: Vec<(u128, Polynomial)> = factorize(n as u128)
+        .into_iter()
+        .map(|(r, _)| n as u128 / r)
+        .filter(|&m| m > 1)
+        .map(|m| {
+            (
+                order / (p.pow(m as u32) - 1),
+                Polynomial::conway(char, m as usize),
+            )
+        })
+        .collect();
+    let elem = |c: u128| FiniteField {
+        char,
+        element: Element::PrimeField {
+            element: c as NumType,
+        },
+    };
+
+    // f = x^n - a_{n-1} x^{n-1} + a_{n-2} x^{n-2} - ..., ordered by (a_{n-1}, ..., a_0).
+    // For n > 1, compatibility with C(p, 1) = x - g says that the norm a_0 of a root is g.
+    let (start, step) = if n > 1 {
+        let g = match Polynomial::conway(char, 1).coef[0].element {
+            Element::PrimeField { element } => (p - element as u128) % p,
+            Element::GaloisField { .. } => unreachable!(),
+        };
+        (g, p as usize)
+    } else {
+        (0, 1)
+    };
+    for i in (start..order + 1).step_by(step) {
+        let mut coef = vec![elem(0); n + 1];
+        coef[n] = elem(1);
+        let mut digits = i;
+        for (j, c) in coef.iter_mut().enumerate().take(n) {
+            let a = elem(digits % p);
+            digits /= p;
+            *c = if (n - j).is_multiple_of(2) { a } else { -a };
+        }
+        if coef[0].is_0() {
+            continue;
+        }
+        let f = Polynomial { coef };
+        let x = Polynomial {
+            coef: vec![elem(0), elem(1)],
+        };
+        let compatible = subfields.iter().all(|(e, g)| {
+            // g(x^e) mod f by Horner's method
+            let y = x.pow_mod(*e, &f);
+            let mut value = Polynomial {
+                coef: vec![elem(0)],
+            };
+            for c in g.coef.iter().rev() {
+                value = (value * y.clone()
+                    + Polynomial {
+                        coef: vec![c.clone()],
+                    })
+                    % f.clone();
+            }
+            value.coef.iter().all(|c| c.is_0())
+        });
+        // x has order exactly p^n - 1, which also makes f irreducible
+        let one = Polynomial {
+            coef: vec![elem(1)],
+        };
+        if compatible
+            && x.pow_mod(order, &f) == one
+            && factors
+                .iter()
+                .all(|&(r, _)| x.pow_mod(order / r, &f) != one)
+        {
+            return f;
+        }
+    }
+    unreachable!("Conway polynomials exist for every p and n")
+}
+
+// (p, n, coefficients in ascending order), sorted by (p, n)
+#[rustfmt::skip]
+const CONWAY: &[(u64, usize, &[u32])] = &[
+    (2, 1, &[1, 1]),
+    (2, 2, &[1, 1, 1]),
+    (2, 3, &[1, 1, 0, 1]),
+    (2, 4, &[1, 1, 0, 0, 1]),
+    (2, 5, &[1, 0, 1, 0, 0, 1]),
+    (2, 6, &[1, 1, 0, 1, 1, 0, 1]),
+    (2, 7, &[1, 1, 0, 0, 0, 0, 0, 1]),
+    (2, 8, &[1, 0, 1, 1, 1, 0, 0, 0, 1]),
+    (2, 9, &[1, 0, 0, 0, 1, 0, 0, 0, 0, 1]),
+    (2, 10, &[1, 1, 1, 1, 0, 1, 1, 0, 0, 0, 1]),
+    (2, 11, &[1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 12, &[1, 1, 0, 1, 0, 1, 1, 1, 0, 0, 0, 0, 1]),
+    (2, 13, &[1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 14, &[1, 0, 0, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 15, &[1, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 16, &[1, 0, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 17, &[1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 18, &[1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1]),
+    (2, 19, &[1, 1, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 20, &[1, 1, 0, 0, 1, 1, 1, 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 21, &[1, 0, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 22, &[1, 0, 0, 0, 0, 1, 1, 0, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 23, &[1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (2, 24, &[1, 0, 0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (3, 1, &[1, 1]),
+    (3, 2, &[2, 2, 1]),
+    (3, 3, &[1, 2, 0, 1]),
+    (3, 4, &[2, 0, 0, 2, 1]),
+    (3, 5, &[1, 2, 0, 0, 0, 1]),
+    (3, 6, &[2, 2, 1, 0, 2, 0, 1]),
+    (3, 7, &[1, 0, 2, 0, 0, 0, 0, 1]),
+    (3, 8, &[2, 2, 2, 0, 1, 2, 0, 0, 1]),
+    (3, 9, &[1, 1, 2, 2, 0, 0, 0, 0, 0, 1]),
+    (3, 10, &[2, 1, 0, 0, 2, 2, 2, 0, 0, 0, 1]),
+    (3, 11, &[1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (3, 12, &[2, 0, 1, 0, 1, 1, 1, 0, 0, 0, 0, 0, 1]),
+    (3, 13, &[1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (3, 14, &[2, 0, 1, 2, 0, 1, 2, 1, 1, 2, 0, 0, 0, 0, 1]),
+    (5, 1, &[3, 1]),
+    (5, 2, &[2, 4, 1]),
+    (5, 3, &[3, 3, 0, 1]),
+    (5, 4, &[2, 4, 4, 0, 1]),
+    (5, 5, &[3, 4, 0, 0, 0, 1]),
+    (5, 6, &[2, 0, 1, 4, 1, 0, 1]),
+    (5, 7, &[3, 3, 0, 0, 0, 0, 0, 1]),
+    (5, 8, &[2, 4, 3, 0, 1, 0, 0, 0, 1]),
+    (5, 9, &[3, 1, 0, 2, 0, 0, 0, 0, 0, 1]),
+    (5, 10, &[2, 1, 4, 2, 3, 3, 0, 0, 0, 0, 1]),
+    (5, 11, &[3, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+    (7, 1, &[4, 1]),
+    (7, 2, &[3, 6, 1]),
+    (7, 3, &[4, 0, 6, 1]),
+    (7, 4, &[3, 4, 5, 0, 1]),
+    (7, 5, &[4, 1, 0, 0, 0, 1]),
+    (7, 6, &[3, 6, 4, 5, 1, 0, 1]),
+    (7, 7, &[4, 6, 0, 0, 0, 0, 0, 1]),
+    (7, 8, &[3, 2, 6, 4, 0, 0, 0, 0, 1]),
+    (7, 9, &[4, 6, 0, 1, 6, 0, 0, 0, 0, 1]),
+    (11, 1, &[9, 1]),
+    (11, 2, &[2, 7, 1]),
+    (11, 3, &[9, 2, 0, 1]),
+    (11, 4, &[2, 10, 8, 0, 1]),
+    (11, 5, &[9, 0, 10, 0, 0, 1]),
+    (11, 6, &[2, 7, 6, 4, 3, 0, 1]),
+    (11, 7, &[9, 4, 0, 0, 0, 0, 0, 1]),
+    (13, 1, &[11, 1]),
+    (13, 2, &[2, 12, 1]),
+    (13, 3, &[11, 2, 0, 1]),
+    (13, 4, &[2, 12, 3, 0, 1]),
+    (13, 5, &[11, 4, 0, 0, 0, 1]),
+    (13, 6, &[2, 11, 11, 10, 0, 0, 1]),
+    (13, 7, &[11, 3, 0, 0, 0, 0, 0, 1]),
+    (17, 1, &[14, 1]),
+    (17, 2, &[3, 16, 1]),
+    (17, 3, &[14, 1, 0, 1]),
+    (17, 4, &[3, 10, 7, 0, 1]),
+    (17, 5, &[14, 1, 0, 0, 0, 1]),
+    (19, 1, &[17, 1]),
+    (19, 2, &[2, 18, 1]),
+    (19, 3, &[17, 4, 0, 1]),
+    (19, 4, &[2, 11, 2, 0, 1]),
+    (19, 5, &[17, 5, 0, 0, 0, 1]),
+    (19, 6, &[2, 6, 17, 17, 0, 0, 1]),
+    (19, 7, &[17, 6, 0, 0, 0, 0, 0, 1]),
+    (23, 1, &[18, 1]),
+    (23, 2, &[5, 21, 1]),
+    (23, 3, &[18, 2, 0, 1]),
+    (23, 4, &[5, 19, 3, 0, 1]),
+    (23, 5, &[18, 3, 0, 0, 0, 1]),
+    (29, 1, &[27, 1]),
+    (29, 2, &[2, 24, 1]),
+    (29, 3, &[27, 2, 0, 1]),
+    (29, 4, &[2, 15, 2, 0, 1]),
+    (29, 5, &[27, 3, 0, 0, 0, 1]),
+    (31, 1, &[28, 1]),
+    (31, 2, &[3, 29, 1]),
+    (31, 3, &[28, 1, 0, 1]),
+    (31, 4, &[3, 16, 3, 0, 1]),
+    (31, 5, &[28, 7, 0, 0, 0, 1]),
+    (37, 1, &[35, 1]),
+    (37, 2, &[2, 33, 1]),
+    (37, 3, &[35, 6, 0, 1]),
+    (37, 4, &[2, 24, 6, 0, 1]),
+    (37, 5, &[35, 10, 0, 0, 0, 1]),
+    (41, 1, &[35, 1]),
+    (41, 2, &[6, 38, 1]),
+    (41, 3, &[35, 1, 0, 1]),
+    (41, 4, &[6, 23, 0, 0, 1]),
+    (41, 5, &[35, 14, 40, 0, 0, 1]),
+    (43, 1, &[40, 1]),
+    (43, 2, &[3, 42, 1]),
+    (43, 3, &[40, 1, 0, 1]),
+    (43, 4, &[3, 42, 5, 0, 1]),
+    (43, 5, &[40, 8, 0, 0, 0, 1]),
+    (47, 1, &[42, 1]),
+    (47, 2, &[5, 45, 1]),
+    (47, 3, &[42, 3, 0, 1]),
+    (47, 4, &[5, 40, 8, 0, 1]),
+    (47, 5, &[42, 1, 0, 0, 0, 1]),
+    (53, 1, &[51, 1]),
+    (53, 2, &[2, 49, 1]),
+    (53, 3, &[51, 3, 0, 1]),
+    (53, 4, &[2, 38, 9, 0, 1]),
+    (53, 5, &[51, 3, 0, 0, 0, 1]),
+    (59, 1, &[57, 1]),
+    (59, 2, &[2, 58, 1]),
+    (59, 3, &[57, 5, 0, 1]),
+    (59, 4, &[2, 40, 2, 0, 1]),
+    (59, 5, &[57, 8, 0, 0, 0, 1]),
+    (61, 1, &[59, 1]),
+    (61, 2, &[2, 60, 1]),
+    (61, 3, &[59, 7, 0, 1]),
+    (61, 4, &[2, 40, 3, 0, 1]),
+    (61, 5, &[59, 12, 0, 0, 0, 1]),
+    (67, 1, &[65, 1]),
+    (67, 2, &[2, 63, 1]),
+    (67, 3, &[65, 6, 0, 1]),
+    (67, 4, &[2, 54, 8, 0, 1]),
+    (67, 5, &[65, 2, 0, 0, 0, 1]),
+    (71, 1, &[64, 1]),
+    (71, 2, &[7, 69, 1]),
+    (71, 3, &[64, 4, 0, 1]),
+    (71, 4, &[7, 41, 4, 0, 1]),
+    (71, 5, &[64, 18, 0, 0, 0, 1]),
+    (73, 1, &[68, 1]),
+    (73, 2, &[5, 70, 1]),
+    (73, 3, &[68, 2, 0, 1]),
+    (79, 1, &[76, 1]),
+    (79, 2, &[3, 78, 1]),
+    (79, 3, &[76, 9, 0, 1]),
+    (79, 4, &[3, 66, 2, 0, 1]),
+    (79, 5, &[76, 5, 0, 0, 0, 1]),
+    (83, 1, &[81, 1]),
+    (83, 2, &[2, 82, 1]),
+    (83, 3, &[81, 3, 0, 1]),
+    (83, 4, &[2, 42, 4, 0, 1]),
+    (83, 5, &[81, 9, 0, 0, 0, 1]),
+    (89, 1, &[86, 1]),
+    (89, 2, &[3, 82, 1]),
+    (89, 3, &[86, 3, 0, 1]),
+    (89, 4, &[3, 72, 4, 0, 1]),
+    (89, 5, &[86, 1, 0, 0, 0, 1]),
+    (97, 1, &[92, 1]),
+    (97, 2, &[5, 96, 1]),
+    (97, 3, &[92, 9, 0, 1]),
+    (97, 4, &[5, 80, 6, 0, 1]),
+    (97, 5, &[92, 3, 0, 0, 0, 1]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_matches_definition() {
+        for &(p, n, coef) in CONWAY
+            .iter()
+            .filter(|&&(p, n, _)| (p as u128).pow(n as u32) < 5000)
+        {
+            let expected: Vec<NumType> = coef.iter().map(|&c| c as NumType).collect();
+            let computed = compute_conway(p, n);
+            let computed: Vec<NumType> = computed
+                .coef
+                .iter()
+                .map(|c| match c.element {
+                    Element::PrimeField { element } => element,
+                    _ => unreachable!(),
+                })
+                .collect();
+            assert_eq!(computed, expected, "C({}, {})", p, n);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        let coef = |p: u64, n: usize| -> Vec<NumType> {
+            Polynomial::conway(p, n)
+                .coef
+                .iter()
+                .map(|c| match c.element {
+                    Element::PrimeField { element } => element,
+                    _ => unreachable!(),
+                })
+                .collect()
+        };
+        assert_eq!(coef(2, 4), vec![1, 1, 0, 0, 1]);
+        assert_eq!(coef(3, 6), vec![2, 2, 1, 0, 2, 0, 1]);
+        assert_eq!(coef(5, 1), vec![3, 1]);
+        assert_eq!(coef(7, 3), vec![4, 0, 6, 1]);
+        assert_eq!(
+            coef(2, 16),
+            vec![1, 0, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        // not in the table
+        assert_eq!(coef(101, 1), vec![99, 1]);
+        assert_eq!(coef(101, 2), vec![2, 97, 1]);
+        assert_eq!(coef(101, 3), vec![99, 3, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "C(101, 5) is not in the table and p^n is not below 2^32")]
+    fn beyond_the_search_limit() {
+        Polynomial::conway(101, 5);
+    }
+}
@@ -0,0 +1,200 @@
+//! Discrete logarithms in the multiplicative group of F_p or GF(p^n), by Pohlig-Hellman
+//! with baby-step giant-step or Pollard's rho for each prime factor of the order.
+
+use crate::integer::{crt, factorize, inv_mod, mul_mod};
+use crate::{coefficient_modulus, Element, Field, FiniteField, NumType};
+use std::collections::HashMap;
+
+// prime orders up to this are solved by baby-step giant-step, larger ones by Pollard's rho
+pub(crate) const BSGS_LIMIT: u128 = 1 << 24;
+
+impl FiniteField {
+    /// The least x >= 0 with base^x = self, or None if self is not in the subgroup generated by `base`.
+    ///
+    /// q - 1 is factored once and x is found modulo each prime power dividing the order of `base`
+    /// by Pohlig-Hellman, so the cost is governed by the square root of its largest prime factor.
+    /// Panics if `base` is 0.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 8);
+    /// let g = gf.gen();
+    /// assert_eq!(g.pow(200).discrete_log(&g), Some(200));
+    /// // x^5 generates the subgroup of order 51, which x does not lie in
+    /// assert_eq!(g.pow(10).discrete_log(&g.pow(5)), Some(2));
+    /// assert_eq!(g.discrete_log(&g.pow(5)), None);
+    /// ```
+    pub fn discrete_log(&self, base: &FiniteField) -> Option<u128> {
+        // q - 1 is factored once; n divides it, so its prime factors are among these
+        let factors = factorize(Field::order(base) - 1);
+        let n = base.multiplicative_order_with(&factors);
+        // the group is cyclic, so the subgroup of order n is the kernel of y -> y^n
+        if self.is_0() || !self.pow(n).is_1() {
+            return None;
+        }
+        let mut x = 0;
+        let mut modulus = 1;
+        for &(r, _) in &factors {
+            let mut e = 0;
+            let mut re = 1;
+            while (n / re).is_multiple_of(r) {
+                re *= r;
+                e += 1;
+            }
+            if e == 0 {
+                continue;
+            }
+            let xr = prime_power_log(self, base, n, r, e);
+            // x = xr mod r^e, combined with the previous congruences
+            x = crt(x, modulus, xr, re);
+            modulus *= re;
+        }
+        Some(x)
+    }
+}
+
+// x mod r^e, one base r digit at a time in the subgroup of order r
+fn prime_power_log(h: &FiniteField, g: &FiniteField, n: u128, r: u128, e: u32) -> u128 {
+    let gamma = g.pow(n / r);
+    let g_inv = g.pow(n - 1);
+    let mut x = 0;
+    let mut r_k = 1;
+    for k in 0..e {
+        // (h g^(-x))^(n / r^(k+1)) = gamma^(digit)
+        let hk = (h.clone() * g_inv.pow(x)).pow(n / (r_k * r));
+        let digit = if r <= BSGS_LIMIT {
+            baby_step_giant_step(&hk, &gamma, r)
+        } else {
+            pollard_rho(&hk, &gamma, r)
+        };
+        x += digit * r_k;
+        if k + 1 < e {
+            r_k *= r;
+        }
+    }
+    x
+}
+
+// hashable representative of an element
+fn key(a: &FiniteField) -> Vec<NumType> {
+    match &a.element {
+        Element::PrimeField { element } => {
+            vec![element.rem_euclid(coefficient_modulus(a.char))]
+        }
+        Element::GaloisField { element, .. } => element.clone(),
+    }
+}
+
+// x in 0..r with g^x = h, for g of order r and h in the subgroup
+fn baby_step_giant_step(h: &FiniteField, g: &FiniteField, r: u128) -> u128 {
+    let m = (r as f64).sqrt().ceil() as u128;
+    let mut baby = HashMap::new();
+    let mut power = g.get_1();
+    for j in 0..m {
+        baby.entry(key(&power)).or_insert(j);
+        power = power * g.clone();
+    }
+    // g^(-m)
+    let giant = g.pow(r - m % r);
+    let mut y = h.clone();
+    for i in 0..m {
+        if let Some(&j) = baby.get(&key(&y)) {
+            return (i * m + j) % r;
+        }
+        y = y * giant.clone();
+    }
+    unreachable!("h is not a power of g")
+}
+
+// x in 0..r with g^x = h, for g of prime order r and h in the subgroup, by Floyd's cycle finding
+// on the walk y = g^a h^b
+fn pollard_rho(h: &FiniteField, g: &FiniteField, r: u128) -> u128 {
+    let step = |(y, a, b): (FiniteField, u128, u128)| {
+        let s = key(&y).iter().fold(0u64, |acc, &c| {
+            acc.wrapping_mul(0x100000001b3).wrapping_add(c as u64)
+        });
+        match s % 3 {
+            0 => (y * h.clone(), a, (b + 1) % r),
+            1 => (y.clone() * y, 2 * a % r, 2 * b % r),
+            _ => (y * g.clone(), (a + 1) % r, b),
+        }
+    };
+    for start in 1.. {
+        let start_point = (g.pow(start), start % r, 0);
+        let mut tortoise = step(start_point.clone());
+        let mut hare = step(step(start_point));
+        while tortoise.0 != hare.0 {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+        }
+        // g^a1 h^b1 = g^a2 h^b2, so x (b1 - b2) = a2 - a1
+        let (_, a1, b1) = tortoise;
+        let (_, a2, b2) = hare;
+        if let Some(inv) = inv_mod((b1 + r - b2) % r, r) {
+            return mul_mod((a2 + r - a1) % r, inv, r);
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::elements;
+    use crate::{Polynomial, GF};
+
+    #[test]
+    fn small_fields_by_brute_force() {
+        for gf in [GF::new(2, 4), GF::new(3, 3), GF::new(101, 1)] {
+            // the units
+            let elements = elements(&gf).split_off(1);
+            for base in elements.iter().step_by(3) {
+                let mut logs = HashMap::new();
+                let mut power = gf.one();
+                for x in 0..base.multiplicative_order() {
+                    logs.insert(key(&power), x);
+                    power = power * base.clone();
+                }
+                for a in &elements {
+                    assert_eq!(a.discrete_log(base), logs.get(&key(a)).copied());
+                }
+                assert_eq!(gf.zero().discrete_log(base), None);
+            }
+        }
+    }
+
+    #[test]
+    fn large_characteristic() {
+        // p - 1 = 2 3^2 5^2 7 11 13 31 41 61 151 331 1321
+        let p = (1u64 << 61) - 1;
+        let gf = GF::new(p, 1);
+        let g = FiniteField::primitive_element(&gf);
+        for x in [0, 1, 123456789, p as u128 - 2] {
+            assert_eq!(g.pow(x).discrete_log(&g), Some(x));
+        }
+        // GF(p^2) with x^2 + 1, whose order p^2 - 1 = 2^62 (2^60 - 1)
+        let fp = gf.zero();
+        let gf2 = GF::with_modulus(Polynomial {
+            coef: vec![fp.get_1(), fp.clone(), fp.get_1()],
+        });
+        let g = FiniteField::primitive_element(&gf2);
+        let x = 0x2bcd_ef01_2345_6789_abcd_ef01_2345;
+        assert_eq!(g.pow(x).discrete_log(&g), Some(x));
+    }
+
+    #[test]
+    fn rho_for_large_prime_factors() {
+        // p = 2r + 1 with r = 134218433 prime, above the baby-step giant-step limit
+        let gf = GF::new(268436867, 1);
+        let g = gf.elem(&[4]);
+        assert_eq!(g.multiplicative_order(), 134218433);
+        for x in [5, 99999999, 134218432] {
+            assert_eq!(g.pow(x).discrete_log(&g), Some(x));
+        }
+        let generator = FiniteField::primitive_element(&gf);
+        assert_eq!(
+            generator.pow(77777777).discrete_log(&generator),
+            Some(77777777)
+        );
+        assert_eq!(generator.discrete_log(&g), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Prime field F_p whose characteristic is fixed at compile time.
+
+use crate::integer::is_prime_u64;
+use crate::Field;
+use std::fmt;
+use std::ops;
+
+/// Element of the prime field F_P, stored as a single reduced machine word.
+///
+/// Since the modulus is part of the type, elements of different fields cannot be mixed:
+/// ```
+/// use galois_field::*;
+/// let x = Fp::<5>::new(3);
+/// let y = Fp::<5>::new(4);
+/// assert_eq!(x + y, Fp::new(2));
+/// assert_eq!(x * y, Fp::new(2));
+/// assert_eq!(x / y, Fp::new(2));
+/// ```
+/// ```compile_fail
+/// use galois_field::*;
+/// let x = Fp::<3>::new(1) + Fp::<5>::new(1);
+/// ```
+/// P must be a prime, otherwise constructing an element fails to compile.
+/// ```compile_fail
+/// use galois_field::*;
+/// let x = Fp::<4>::default();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    const MODULUS_IS_PRIME: () = assert!(is_prime_u64(P), "the modulus of Fp must be a prime");
+
+    /// Element x mod P.
+    pub fn new(x: u64) -> Fp<P> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::MODULUS_IS_PRIME;
+        Fp(x % P)
+    }
+
+    /// Element x mod P for a signed integer, e.g. -1 -> P-1.
+    pub fn from_i64(x: i64) -> Fp<P> {
+        Fp::new((x as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// Representative in 0..P.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<const P: u64> Default for Fp<P> {
+    fn default() -> Fp<P> {
+        Fp::new(0)
+    }
+}
+
+impl<const P: u64> From<u64> for Fp<P> {
+    fn from(x: u64) -> Fp<P> {
+        Fp::new(x)
+    }
+}
+
+impl<const P: u64> fmt::Display for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> ops::Add for Fp<P> {
+    type Output = Fp<P>;
+    fn add(self, other: Fp<P>) -> Fp<P> {
+        Fp(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> ops::Sub for Fp<P> {
+    type Output = Fp<P>;
+    fn sub(self, other: Fp<P>) -> Fp<P> {
+        if self.0 >= other.0 {
+            Fp(self.0 - other.0)
+        } else {
+            Fp(P - (other.0 - self.0))
+        }
+    }
+}
+
+impl<const P: u64> ops::Mul for Fp<P> {
+    type Output = Fp<P>;
+    fn mul(self, other: Fp<P>) -> Fp<P> {
+        Fp(((self.0 as u128 * other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> ops::Div for Fp<P> {
+    type Output = Fp<P>;
+    // Dividing by 0 panics, as in every field of the crate.
+    fn div(self, other: Fp<P>) -> Fp<P> {
+        assert!(other.0 != 0, "division by zero");
+        self * other.inv()
+    }
+}
+
+impl<const P: u64> ops::Neg for Fp<P> {
+    type Output = Fp<P>;
+    fn neg(self) -> Fp<P> {
+        Fp(0) - self
+    }
+}
+
+impl<const P: u64> Field for Fp<P> {
+    fn zero(&self) -> Fp<P> {
+        Fp(0)
+    }
+    fn one(&self) -> Fp<P> {
+        Fp::new(1)
+    }
+    fn inv(&self) -> Fp<P> {
+        assert!(self.0 != 0, "0 has no inverse");
+        // extended Euclidean algorithm on (P, x)
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        Fp(t0.rem_euclid(P as i128) as u64)
+    }
+    fn characteristic(&self) -> u64 {
+        P
+    }
+    fn order(&self) -> u128 {
+        P as u128
+    }
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+    fn is_one(&self) -> bool {
+        self.0 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polynomial;
+
+    #[test]
+    fn matches_prime_field_element() {
+        use crate::{Element, FiniteField};
+        let ff = |x: i64| FiniteField {
+            char: 7,
+            element: Element::PrimeField { element: x },
+        };
+        for x in 0..7 {
+            for y in 0..7 {
+                let (a, b) = (Fp::<7>::new(x), Fp::<7>::new(y));
+                let (c, d) = (ff(x as i64), ff(y as i64));
+                assert_eq!(ff((a + b).value() as i64), c.clone() + d.clone());
+                assert_eq!(ff((a - b).value() as i64), c.clone() - d.clone());
+                assert_eq!(ff((a * b).value() as i64), c.clone() * d.clone());
+                if y != 0 {
+                    assert_eq!(ff((a / b).value() as i64), c.clone() / d.clone());
+                }
+                assert_eq!(ff((-a).value() as i64), -c);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let _ = Fp::<7>::new(1) / Fp::<7>::new(0);
+    }
+
+    #[test]
+    fn large_modulus() {
+        // 2^61 - 1
+        type F = Fp<2305843009213693951>;
+        let x = F::new(1 << 60);
+        assert_eq!(x * x.inv(), F::new(1));
+        assert_eq!(x * x, F::new(1 << 59));
+        assert_eq!(F::from_i64(-1), F::new(2305843009213693950));
+        // 2^64 - 59, above i64::MAX
+        type G = Fp<18446744073709551557>;
+        assert_eq!(G::from_i64(-1), G::new(18446744073709551556));
+        assert_eq!(G::from_i64(i64::MIN), G::new((1 << 63) - 59));
+        assert_eq!(G::default(), G::new(0));
+    }
+
+    #[test]
+    fn polynomial_over_fp() {
+        let poly = |c: &[u64]| Polynomial {
+            coef: c.iter().map(|&x| Fp::<5>::new(x)).collect(),
+        };
+        // x^2 - 1 = (x + 1)(x - 1)
+        assert_eq!(poly(&[4, 0, 1]) / poly(&[1, 1]), poly(&[4, 1]));
+        assert_eq!(poly(&[4, 0, 1]).gcd(poly(&[2, 2])), poly(&[2, 2]));
+    }
+}
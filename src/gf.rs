@@ -0,0 +1,992 @@
+//! Field descriptor of GF(p^n) that hands out its elements.
+
+use crate::integer::{divisors, euler_phi, is_prime_u64, mobius};
+use crate::log_table::{LogTables, MAX_TABLE_ORDER};
+use crate::{Element, Field, FiniteField, NumType, Polynomial};
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// The finite field GF(p^n), created once and used to construct elements.
+///
+/// Cloning a `GF` is cheap: every clone, and every element it creates, refers to the same modulus.
+/// ```
+/// use galois_field::*;
+/// let gf = GF::new(2, 4); // GF(2^4) with the primitive polynomial x^4 + x + 1
+/// let x = gf.gen(); // x
+/// let y = gf.elem(&[0, 0, 1, 1]); // x^3 + x^2
+/// assert_eq!(x.clone() * y.clone(), gf.elem(&[1, 1, 0, 1]));
+/// assert_eq!((x.clone() / y.clone()) * y, x);
+/// ```
+/// When n = 1 the elements are `Element::PrimeField`s of F_p.
+///
+/// For fields with at most 2^16 elements, `with_log_tables` switches multiplication and division
+/// to table lookups:
+/// ```
+/// use galois_field::*;
+/// let gf = GF::new(2, 4).with_log_tables();
+/// let x = gf.gen();
+/// assert_eq!(gf.log(&x), Some(1));
+/// // x^20 = x^(20 mod 15)
+/// assert_eq!(gf.exp(20), gf.exp(5));
+/// // x^3 + x^7 = x^(3 + Z(4))
+/// let z = gf.zech_log(4).unwrap();
+/// assert_eq!(gf.exp(3) + gf.exp(7), gf.exp(3 + z as u128));
+/// ```
+#[derive(Clone)]
+pub struct GF {
+    inner: Arc<GFInner>,
+}
+
+struct GFInner {
+    char: u64,
+    degree: usize,
+    modulus: Polynomial,
+    // coefficients of the monic modulus in 0..p, for reduction without `FiniteField` operations
+    modulus_coef: Vec<NumType>,
+    tables: Option<LogTables>,
+    // x^(ip) mod modulus for 0 <= i < n, computed on first use
+    frobenius: OnceLock<Vec<Vec<NumType>>>,
+    // a non-square of GF(p^n) for odd p, computed on first use
+    non_square: OnceLock<Vec<NumType>>,
+    // a basis element x^i of trace 1 in GF(2^n), computed on first use
+    trace_one: OnceLock<Vec<NumType>>,
+}
+
+impl GF {
+    /// GF(p^n) defined by the primitive polynomial of `Polynomial::get_primitive_polynomial`.
+    ///
+    /// Panics if p is not a prime below 2^63 or n = 0.
+    pub fn new(char: u64, n: usize) -> GF {
+        assert!(
+            char < 1 << 63,
+            "characteristic {} is not below 2^63, the limit of i64 coefficients",
+            char
+        );
+        assert!(is_prime_u64(char), "characteristic {} is not a prime", char);
+        assert!(n >= 1, "degree of the extension must be at least 1");
+        let modulus = Polynomial::get_primitive_polynomial(char, n as NumType);
+        GF::from_monic(modulus)
+    }
+
+    /// GF(p^n) = F_p[x] / (modulus) for an irreducible polynomial over F_p of degree n.
+    ///
+    /// The modulus is made monic. Panics if it is not a non-constant irreducible polynomial over a
+    /// prime field of characteristic below 2^63.
+    pub fn with_modulus(modulus: Polynomial) -> GF {
+        let char = modulus.coef[0].char;
+        assert!(
+            char < 1 << 63,
+            "characteristic {} is not below 2^63, the limit of i64 coefficients",
+            char
+        );
+        let modulus = modulus.clone().adjust_func();
+        assert!(is_prime_u64(char), "characteristic {} is not a prime", char);
+        assert!(
+            modulus
+                .coef
+                .iter()
+                .all(|c| c.char == char && matches!(c.element, Element::PrimeField { .. })),
+            "modulus must have coefficients in a single prime field"
+        );
+        assert!(modulus.coef.len() >= 2, "modulus must not be a constant");
+        assert!(modulus.is_irreducible(), "modulus must be irreducible");
+        let lead_inv = modulus.coef[modulus.coef.len() - 1].inv();
+        let monic = Polynomial {
+            coef: modulus
+                .coef
+                .into_iter()
+                .map(|c| c * lead_inv.clone())
+                .collect(),
+        };
+        GF::from_monic(monic)
+    }
+
+    fn from_monic(modulus: Polynomial) -> GF {
+        let modulus_coef = modulus
+            .coef
+            .iter()
+            .map(|c| match c.element {
+                Element::PrimeField { element: e } => e.rem_euclid(c.char as NumType),
+                Element::GaloisField { .. } => unreachable!(),
+            })
+            .collect();
+        GF {
+            inner: Arc::new(GFInner {
+                char: modulus.coef[0].char,
+                degree: modulus.coef.len() - 1,
+                modulus,
+                modulus_coef,
+                tables: None,
+                frobenius: OnceLock::new(),
+                non_square: OnceLock::new(),
+                trace_one: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// The same field with log/antilog and Zech logarithm tables, used for multiplication and division.
+    ///
+    /// Elements of `self` and of the returned field can be mixed. Panics if the field has more than 2^16 elements.
+    pub fn with_log_tables(&self) -> GF {
+        assert!(
+            self.order() <= MAX_TABLE_ORDER,
+            "log tables are only built for fields with at most {} elements",
+            MAX_TABLE_ORDER
+        );
+        let field = GF::from_monic(self.inner.modulus.clone());
+        let tables = LogTables::build(&field);
+        GF {
+            inner: Arc::new(GFInner {
+                tables: Some(tables),
+                ..Arc::try_unwrap(field.inner).ok().unwrap()
+            }),
+        }
+    }
+
+    /// Determine if the field uses log tables.
+    pub fn has_log_tables(&self) -> bool {
+        self.inner.tables.is_some()
+    }
+
+    fn tables(&self) -> &LogTables {
+        self.inner
+            .tables
+            .as_ref()
+            .expect("log tables are not built, use GF::with_log_tables")
+    }
+
+    /// Discrete logarithm of x to the base of the primitive element of the tables, None for 0.
+    pub fn log(&self, x: &FiniteField) -> Option<u32> {
+        self.tables().log(self.coef_to_index(&self.coef_of(x)))
+    }
+
+    /// a^i for the primitive element a of the tables.
+    pub fn exp(&self, i: u128) -> FiniteField {
+        self.elem(&self.index_to_coef(self.tables().exp(i)))
+    }
+
+    /// Zech logarithm Z(n), defined by a^Z(n) = 1 + a^n; None when 1 + a^n = 0.
+    pub fn zech_log(&self, n: u32) -> Option<u32> {
+        self.tables().zech(n)
+    }
+
+    /// Addition in log representation: log(a^i + a^j), where None stands for 0.
+    pub fn add_logs(&self, i: Option<u32>, j: Option<u32>) -> Option<u32> {
+        let order = self.order() as u64 - 1;
+        match (i, j) {
+            (None, k) | (k, None) => k,
+            (Some(i), Some(j)) => {
+                // a^i + a^j = a^i (1 + a^(j-i))
+                let diff = (j as u64 + order - i as u64 % order) % order;
+                self.zech_log(diff as u32)
+                    .map(|z| ((i as u64 + z as u64) % order) as u32)
+            }
+        }
+    }
+
+    /// Characteristic p.
+    pub fn char(&self) -> u64 {
+        self.inner.char
+    }
+
+    /// Degree n of the extension over F_p.
+    pub fn degree(&self) -> usize {
+        self.inner.degree
+    }
+
+    /// Number of elements p^n. Panics if it does not fit in u128.
+    pub fn order(&self) -> u128 {
+        u32::try_from(self.inner.degree)
+            .ok()
+            .and_then(|n| (self.inner.char as u128).checked_pow(n))
+            .unwrap_or_else(|| {
+                panic!(
+                    "GF({}^{}) has 2^128 or more elements",
+                    self.inner.char, self.inner.degree
+                )
+            })
+    }
+
+    /// The monic irreducible polynomial defining the field.
+    pub fn modulus(&self) -> &Polynomial {
+        &self.inner.modulus
+    }
+
+    /// Element with the given coefficients in ascending order, i.e. [1, 0, 1] = x^2 + 1.
+    ///
+    /// Coefficients are reduced mod p. Panics if more than n coefficients are given.
+    pub fn elem(&self, coef: &[NumType]) -> FiniteField {
+        assert!(
+            coef.len() <= self.degree(),
+            "GF({}^{}) element has at most {} coefficients",
+            self.char(),
+            self.degree(),
+            self.degree()
+        );
+        let p = self.char() as NumType;
+        let coef: Vec<NumType> = coef.iter().map(|c| c.rem_euclid(p)).collect();
+        if self.degree() == 1 {
+            return FiniteField {
+                char: self.char(),
+                element: Element::PrimeField {
+                    element: coef.first().copied().unwrap_or(0),
+                },
+            };
+        }
+        FiniteField {
+            char: self.char(),
+            element: Element::GaloisField {
+                element: crate::drop0(coef),
+                field: self.clone(),
+            },
+        }
+    }
+
+    /// Element of the prime field F_p seen as an element of this field.
+    pub fn embed(&self, a: &FiniteField) -> FiniteField {
+        match a.element {
+            Element::PrimeField { element: e } if a.char == self.char() => self.elem(&[e]),
+            _ => panic!("{:?} is not an element of F_{}", a, self.char()),
+        }
+    }
+
+    /// Determine if a lies in the subfield GF(p^m), i.e. a^(p^m) = a.
+    ///
+    /// Panics unless m divides n.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// // x^5 has order 3, so it lies in GF(2^2)
+    /// assert!(gf.is_in_subfield(&gf.gen().pow(5), 2));
+    /// assert!(!gf.is_in_subfield(&gf.gen(), 2));
+    /// ```
+    pub fn is_in_subfield(&self, a: &FiniteField, m: usize) -> bool {
+        assert!(
+            m >= 1 && self.degree().is_multiple_of(m),
+            "GF({}^{}) has no subfield GF({}^{})",
+            self.char(),
+            self.degree(),
+            self.char(),
+            m
+        );
+        let mut b = a.clone();
+        for _ in 0..m {
+            b = b.frobenius();
+        }
+        b == *a
+    }
+
+    /// 0 of the field.
+    pub fn zero(&self) -> FiniteField {
+        self.elem(&[])
+    }
+
+    /// 1 of the field.
+    pub fn one(&self) -> FiniteField {
+        self.elem(&[1])
+    }
+
+    /// The class of x, a root of the modulus.
+    ///
+    /// For n = 1 this is the root -a of the modulus x + a.
+    pub fn gen(&self) -> FiniteField {
+        if self.degree() == 1 {
+            let a = &self.inner.modulus.coef[0];
+            return -a.clone();
+        }
+        self.elem(&[0, 1])
+    }
+
+    /// Every monic polynomial of degree n over this field, with the lower coefficients counted
+    /// as base q digits.
+    pub fn monic_polynomials(&self, n: usize) -> impl Iterator<Item = Polynomial> {
+        let q = self.order();
+        let count = q.checked_pow(n as u32).expect("q^n must fit in u128");
+        let gf = self.clone();
+        (0..count).map(move |mut i| {
+            let mut coef = Vec::with_capacity(n + 1);
+            for _ in 0..n {
+                coef.push(gf.elem(&gf.index_to_coef((i % q) as usize)));
+                i /= q;
+            }
+            coef.push(gf.one());
+            Polynomial { coef }
+        })
+    }
+
+    /// Every monic irreducible polynomial of degree n over this field.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 1);
+    /// let irreducibles: Vec<Polynomial> = gf.irreducible_polynomials(4).collect();
+    /// assert_eq!(irreducibles.len(), 3);
+    /// assert_eq!(gf.count_irreducible_polynomials(4), 3);
+    /// ```
+    pub fn irreducible_polynomials(&self, n: usize) -> impl Iterator<Item = Polynomial> {
+        self.monic_polynomials(n).filter(|f| f.is_irreducible())
+    }
+
+    /// Number of monic irreducible polynomials of degree n over GF(q),
+    /// (1/n) sum of mu(d) q^(n/d) over the divisors d of n.
+    pub fn count_irreducible_polynomials(&self, n: usize) -> u128 {
+        assert!(n >= 1, "degree must be at least 1");
+        let q = self.order();
+        let (mut plus, mut minus) = (0u128, 0u128);
+        for d in divisors(n as u128) {
+            let term = q
+                .checked_pow((n as u128 / d) as u32)
+                .expect("q^n must fit in u128");
+            match mobius(d) {
+                1 => plus += term,
+                -1 => minus += term,
+                _ => {}
+            }
+        }
+        (plus - minus) / n as u128
+    }
+
+    /// Every primitive polynomial of degree n over this field.
+    pub fn primitive_polynomials(&self, n: usize) -> impl Iterator<Item = Polynomial> {
+        self.monic_polynomials(n).filter(|f| f.is_primitive())
+    }
+
+    /// Number of primitive polynomials of degree n over GF(q), phi(q^n - 1) / n.
+    pub fn count_primitive_polynomials(&self, n: usize) -> u128 {
+        assert!(n >= 1, "degree must be at least 1");
+        let order = self
+            .order()
+            .checked_pow(n as u32)
+            .expect("q^n must fit in u128");
+        euler_phi(order - 1) / n as u128
+    }
+
+    /// Irreducible polynomial of degree n with the fewest nonzero terms, up to 5,
+    /// e.g. a trinomial x^n + a x^k + b or a pentanomial.
+    ///
+    /// Among polynomials of the same weight the one with the smallest exponents is returned,
+    /// comparing the highest middle exponent first. None if there is none of weight <= 5.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 1);
+    /// // x^8 + x^4 + x^3 + x + 1, the modulus of AES
+    /// let f = gf.low_weight_irreducible(8).unwrap();
+    /// let aes = [1, 1, 0, 1, 1, 0, 0, 0, 1];
+    /// assert_eq!(f, Polynomial { coef: aes.iter().map(|&c| gf.elem(&[c])).collect() });
+    /// // x^7 + x + 1
+    /// let f = gf.low_weight_primitive(7).unwrap();
+    /// let trinomial = [1, 1, 0, 0, 0, 0, 0, 1];
+    /// assert_eq!(f, Polynomial { coef: trinomial.iter().map(|&c| gf.elem(&[c])).collect() });
+    /// ```
+    pub fn low_weight_irreducible(&self, n: usize) -> Option<Polynomial> {
+        self.low_weight_search(n, &|f| f.is_irreducible())
+    }
+
+    /// Primitive polynomial of degree n with the fewest nonzero terms, up to 5,
+    /// in the same order as `low_weight_irreducible`.
+    pub fn low_weight_primitive(&self, n: usize) -> Option<Polynomial> {
+        self.low_weight_search(n, &|f| f.is_primitive())
+    }
+}
+
+// arithmetic on coefficient vectors of elements, all coefficients in 0..p
+impl GF {
+    /// Panics unless `other` is the same field; clones of one `GF` are recognised by pointer.
+    pub(crate) fn assert_same(&self, other: &GF) {
+        assert!(
+            self == other,
+            "elements of different fields: {:?} and {:?}",
+            self,
+            other
+        );
+    }
+
+    pub(crate) fn add_coef(&self, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        let p = self.char() as i128;
+        let (f, g) = if f.len() < g.len() { (g, f) } else { (f, g) };
+        let mut result = f.to_vec();
+        for (r, &b) in result.iter_mut().zip(g) {
+            *r = ((*r as i128 + b as i128) % p) as NumType;
+        }
+        crate::drop0(result)
+    }
+
+    pub(crate) fn neg_coef(&self, f: &[NumType]) -> Vec<NumType> {
+        let p = self.char() as NumType;
+        crate::drop0(f.iter().map(|&a| (p - a) % p).collect())
+    }
+
+    pub(crate) fn sub_coef(&self, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        self.add_coef(f, &self.neg_coef(g))
+    }
+
+    pub(crate) fn mul_coef(&self, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        if let Some(tables) = &self.inner.tables {
+            return tables.mul(self, f, g);
+        }
+        let p = self.char() as i128;
+        let mut result = vec![0i128; f.len() + g.len()];
+        for (i, &a) in f.iter().enumerate() {
+            if a == 0 {
+                continue;
+            }
+            for (j, &b) in g.iter().enumerate() {
+                result[i + j] = (result[i + j] + a as i128 * b as i128) % p;
+            }
+        }
+        self.reduce(result)
+    }
+
+    /// f / g. Panics if g = 0.
+    pub(crate) fn div_coef(&self, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        if let Some(tables) = &self.inner.tables {
+            return tables.div(self, f, g);
+        }
+        let p = self.char() as NumType;
+        assert!(g.iter().any(|&c| c % p != 0), "division by zero");
+        self.mul_coef(f, &self.inv_coef(g))
+    }
+
+    /// Inverse of a nonzero g by the extended Euclidean algorithm on (modulus, g) over F_p.
+    pub(crate) fn inv_coef(&self, g: &[NumType]) -> Vec<NumType> {
+        let p = self.char() as NumType;
+        // r0 = t0 * g, r1 = t1 * g (mod modulus)
+        let mut r0 = self.inner.modulus_coef.clone();
+        let mut r1 = crate::drop0(g.to_vec());
+        let mut t0: Vec<NumType> = vec![0];
+        let mut t1: Vec<NumType> = vec![1];
+        while !(r1.len() == 1 && r1[0] == 0) {
+            let (q, r) = poly_divrem(&r0, &r1, p);
+            let t = self.sub_coef(&t0, &poly_mul(&q, &t1, p));
+            (r0, r1) = (r1, r);
+            (t0, t1) = (t1, t);
+        }
+        // r0 is a nonzero constant since the modulus is irreducible
+        assert!(r0.len() == 1, "modulus of {:?} is not irreducible", self);
+        let c_inv = crate::extended_euclidean(p, r0[0]);
+        crate::drop0(
+            t0.iter()
+                .map(|&t| (t as i128 * c_inv as i128 % p as i128) as NumType)
+                .collect(),
+        )
+    }
+
+    /// f^e by square-and-multiply, or by the log tables when present.
+    pub(crate) fn pow_coef(&self, f: &[NumType], e: &[u64]) -> Vec<NumType> {
+        if let Some(tables) = &self.inner.tables {
+            // e mod (q-1)
+            let m = self.order() - 1;
+            let e_mod = e
+                .iter()
+                .rev()
+                .fold(0, |acc, &l| ((acc << 64) | l as u128) % m);
+            let e_is_zero = e.iter().all(|&l| l == 0);
+            return match tables.log(self.coef_to_index(f)) {
+                Some(l) => self.index_to_coef(tables.exp(l as u128 * e_mod)),
+                None if e_is_zero => vec![1],
+                None => vec![0],
+            };
+        }
+        let mut result: Vec<NumType> = vec![1];
+        for bit in crate::exponent_bits(e) {
+            result = self.mul_coef(&result, &result);
+            if bit {
+                result = self.mul_coef(&result, f);
+            }
+        }
+        result
+    }
+
+    /// f^p as the F_p-linear combination of x^(ip) mod modulus.
+    pub(crate) fn frobenius_coef(&self, f: &[NumType]) -> Vec<NumType> {
+        let images = self.inner.frobenius.get_or_init(|| {
+            let x_p = self.pow_coef(&[0, 1], &[self.char()]);
+            let mut images = vec![vec![1]];
+            for i in 1..self.degree() {
+                images.push(self.mul_coef(&images[i - 1], &x_p));
+            }
+            images
+        });
+        let p = self.char() as i128;
+        let mut result = vec![0i128; self.degree()];
+        for (&a, image) in f.iter().zip(images) {
+            for (r, &b) in result.iter_mut().zip(image) {
+                *r = (*r + a as i128 * b as i128) % p;
+            }
+        }
+        crate::drop0(result.into_iter().map(|c| c as NumType).collect())
+    }
+
+    /// Coefficients of the first non-square outside F_p in index order.
+    ///
+    /// Panics unless p is odd and n > 1.
+    pub(crate) fn non_square_coef(&self) -> &[NumType] {
+        assert!(
+            self.char() != 2 && self.degree() > 1,
+            "a non-square outside F_p needs odd p and n > 1, not GF({}^{})",
+            self.char(),
+            self.degree()
+        );
+        self.inner.non_square.get_or_init(|| {
+            // for n > 1 half of the elements outside F_p are non-squares
+            (self.char() as usize..)
+                .map(|i| self.index_to_coef(i))
+                .find(|c| self.elem(c).legendre() == -1)
+                .unwrap()
+        })
+    }
+
+    /// Coefficients of the first x^i of trace 1, for p = 2.
+    pub(crate) fn trace_one_coef(&self) -> &[NumType] {
+        self.inner.trace_one.get_or_init(|| {
+            // the trace is a nonzero linear map, so it is 1 on some element of the basis
+            (0..self.degree())
+                .map(|i| {
+                    let mut coef = vec![0; i + 1];
+                    coef[i] = 1;
+                    coef
+                })
+                .find(|c| self.elem(c).trace().is_1())
+                .unwrap()
+        })
+    }
+
+    // first polynomial of weight 2, ..., 5 passing the test
+    fn low_weight_search(
+        &self,
+        n: usize,
+        test: &dyn Fn(&Polynomial) -> bool,
+    ) -> Option<Polynomial> {
+        assert!(n >= 1, "degree must be at least 1");
+        (0..=3.min(n - 1)).find_map(|k| self.search_exponents(n, k, &mut Vec::new(), n, test))
+    }
+
+    // chooses k middle exponents in descending order, each below the previous one
+    fn search_exponents(
+        &self,
+        n: usize,
+        k: usize,
+        chosen: &mut Vec<usize>,
+        below: usize,
+        test: &dyn Fn(&Polynomial) -> bool,
+    ) -> Option<Polynomial> {
+        if chosen.len() == k {
+            return self.search_coefficients(n, chosen, test);
+        }
+        for a in (k - chosen.len())..below {
+            chosen.push(a);
+            if let Some(f) = self.search_exponents(n, k, chosen, a, test) {
+                return Some(f);
+            }
+            chosen.pop();
+        }
+        None
+    }
+
+    // tries every nonzero coefficient for the constant term and the given middle exponents
+    fn search_coefficients(
+        &self,
+        n: usize,
+        exponents: &[usize],
+        test: &dyn Fn(&Polynomial) -> bool,
+    ) -> Option<Polynomial> {
+        let q = self.order() as usize;
+        let positions: Vec<usize> = std::iter::once(0)
+            .chain(exponents.iter().copied())
+            .collect();
+        let mut digits = vec![1; positions.len()];
+        loop {
+            let mut coef = vec![self.zero(); n + 1];
+            coef[n] = self.one();
+            for (&i, &d) in positions.iter().zip(&digits) {
+                coef[i] = self.elem(&self.index_to_coef(d));
+            }
+            let f = Polynomial { coef };
+            if test(&f) {
+                return Some(f);
+            }
+            // next assignment of digits in 1..q
+            let mut i = 0;
+            loop {
+                if i == digits.len() {
+                    return None;
+                }
+                digits[i] += 1;
+                if digits[i] < q {
+                    break;
+                }
+                digits[i] = 1;
+                i += 1;
+            }
+        }
+    }
+
+    /// Element number i, counting with the coefficients as base p digits: [1, 1] = 1 + p.
+    pub(crate) fn index_to_coef(&self, mut i: usize) -> Vec<NumType> {
+        let p = self.char() as usize;
+        let mut coef = Vec::with_capacity(self.degree());
+        while i > 0 {
+            coef.push((i % p) as NumType);
+            i /= p;
+        }
+        crate::drop0(coef)
+    }
+
+    /// Inverse of `index_to_coef`.
+    pub(crate) fn coef_to_index(&self, coef: &[NumType]) -> usize {
+        let p = self.char() as usize;
+        coef.iter().rev().fold(0, |acc, &c| acc * p + c as usize)
+    }
+
+    // coefficients of an element of this field
+    pub(crate) fn coef_of(&self, x: &FiniteField) -> Vec<NumType> {
+        match &x.element {
+            Element::PrimeField { element } if self.degree() == 1 && x.char == self.char() => {
+                vec![element.rem_euclid(self.char() as NumType)]
+            }
+            Element::GaloisField { element, field } => {
+                self.assert_same(field);
+                element.clone()
+            }
+            _ => panic!("{:?} is not an element of {:?}", x, self),
+        }
+    }
+
+    // remainder modulo the monic modulus
+    fn reduce(&self, mut v: Vec<i128>) -> Vec<NumType> {
+        let p = self.char() as i128;
+        let n = self.degree();
+        let modulus = &self.inner.modulus_coef;
+        for i in (n..v.len()).rev() {
+            let lead = v[i] % p;
+            if lead == 0 {
+                continue;
+            }
+            for (j, &m) in modulus.iter().enumerate() {
+                v[i - n + j] = (v[i - n + j] - lead * m as i128).rem_euclid(p);
+            }
+        }
+        v.truncate(n);
+        crate::drop0(v.into_iter().map(|c| c as NumType).collect())
+    }
+}
+
+// product of polynomials over F_p, without reduction
+fn poly_mul(f: &[NumType], g: &[NumType], p: NumType) -> Vec<NumType> {
+    let mut result = vec![0i128; f.len() + g.len() - 1];
+    for (i, &a) in f.iter().enumerate() {
+        for (j, &b) in g.iter().enumerate() {
+            result[i + j] = (result[i + j] + a as i128 * b as i128) % p as i128;
+        }
+    }
+    crate::drop0(result.into_iter().map(|c| c as NumType).collect())
+}
+
+// quotient and remainder of polynomials over F_p, g != 0
+fn poly_divrem(f: &[NumType], g: &[NumType], p: NumType) -> (Vec<NumType>, Vec<NumType>) {
+    let mut r: Vec<i128> = f.iter().map(|&c| c as i128).collect();
+    if f.len() < g.len() {
+        return (vec![0], f.to_vec());
+    }
+    let p = p as i128;
+    let lead_inv = crate::extended_euclidean(p as NumType, g[g.len() - 1]) as i128;
+    let mut q = vec![0; f.len() - g.len() + 1];
+    for i in (0..q.len()).rev() {
+        let c = r[i + g.len() - 1] * lead_inv % p;
+        q[i] = c as NumType;
+        for (j, &b) in g.iter().enumerate() {
+            r[i + j] = (r[i + j] - c * b as i128).rem_euclid(p);
+        }
+    }
+    r.truncate(g.len() - 1);
+    let r = crate::drop0(r.into_iter().map(|c| c as NumType).collect());
+    (crate::drop0(q), r)
+}
+
+impl PartialEq for GF {
+    fn eq(&self, other: &GF) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+            || (self.inner.char == other.inner.char
+                && self.inner.modulus_coef == other.inner.modulus_coef)
+    }
+}
+
+impl Eq for GF {}
+
+impl fmt::Debug for GF {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GF")
+            .field("char", &self.inner.char)
+            .field("degree", &self.inner.degree)
+            .field("modulus", &self.inner.modulus_coef)
+            .field("log_tables", &self.has_log_tables())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elements_share_the_modulus() {
+        let gf = GF::new(3, 2);
+        let x = gf.gen();
+        assert_eq!(x.order(), 9);
+        // x is a root of the modulus
+        let mut modulus = Polynomial {
+            coef: gf.modulus().coef.iter().map(|c| gf.embed(c)).collect(),
+        };
+        assert!(modulus.assign_value(x.clone()).is_0());
+        assert_eq!(gf.elem(&[4, -1]), gf.elem(&[1, 2]));
+        assert_eq!(x.clone() * x.inv(), gf.one());
+    }
+
+    #[test]
+    fn user_modulus() {
+        // AES: x^8 + x^4 + x^3 + x + 1
+        let coef = [1, 1, 0, 1, 1, 0, 0, 0, 1];
+        let modulus = Polynomial {
+            coef: coef
+                .iter()
+                .map(|&c| FiniteField {
+                    char: 2,
+                    element: Element::PrimeField { element: c },
+                })
+                .collect(),
+        };
+        let gf = GF::with_modulus(modulus);
+        assert_eq!(gf.degree(), 8);
+        // {57} * {83} = {c1}
+        let a = gf.elem(&[1, 1, 1, 0, 1, 0, 1]);
+        let b = gf.elem(&[1, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(a * b, gf.elem(&[1, 0, 0, 0, 0, 0, 1, 1]));
+    }
+
+    #[test]
+    fn clones_are_the_same_field() {
+        let gf = GF::new(2, 3);
+        assert!(Arc::ptr_eq(&gf.inner, &gf.clone().inner));
+        if let Element::GaloisField { field, .. } = &gf.gen().element {
+            assert!(Arc::ptr_eq(&gf.inner, &field.inner));
+        }
+        // a separately built GF(2^3) with the same modulus is equal
+        assert_eq!(gf.gen() * GF::new(2, 3).gen(), gf.elem(&[0, 0, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "different fields")]
+    fn mixing_fields() {
+        let _ = GF::new(2, 3).gen() + GF::new(2, 4).gen();
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let gf = GF::new(3, 2);
+        let _ = gf.gen() / gf.zero();
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero_with_log_tables() {
+        let gf = GF::new(3, 2).with_log_tables();
+        let _ = gf.gen() / gf.zero();
+    }
+
+    #[test]
+    fn log_tables_match_polynomial_arithmetic() {
+        // x^3 + 2x + 1 over F_3 has x as a primitive element, x^3 + x^2 + x + 2 does not
+        for coef in [[1, 2, 0, 1], [2, 1, 1, 1]] {
+            let modulus = Polynomial {
+                coef: coef
+                    .iter()
+                    .map(|&c| FiniteField {
+                        char: 3,
+                        element: Element::PrimeField { element: c },
+                    })
+                    .collect(),
+            };
+            let plain = GF::with_modulus(modulus);
+            let table = plain.with_log_tables();
+            for i in 0..27 {
+                for j in 0..27 {
+                    let (a, b) = (plain.index_to_coef(i), plain.index_to_coef(j));
+                    let (c, d) = (plain.elem(&a), plain.elem(&b));
+                    let (e, f) = (table.elem(&a), table.elem(&b));
+                    assert_eq!(c.clone() * d.clone(), e.clone() * f.clone());
+                    if j != 0 {
+                        assert_eq!(c.clone() / d.clone(), e.clone() / f.clone());
+                    }
+                    let log_sum = table.add_logs(table.log(&e), table.log(&f));
+                    assert_eq!(
+                        log_sum.map(|l| table.exp(l as u128)),
+                        Some(c + d).filter(|s| !s.is_0())
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn default_modulus_is_primitive() {
+        // GF(2^8) used to get the AES modulus, where x has order 51
+        let gf = GF::new(2, 8).with_log_tables();
+        assert_eq!(gf.log(&gf.gen()), Some(1));
+        assert!(gf.modulus().is_primitive());
+    }
+
+    #[test]
+    fn irreducible_counts() {
+        for (p, m) in [(2, 1), (3, 1), (2, 2), (5, 1)] {
+            let gf = GF::new(p, m);
+            for n in 1..=4 {
+                if gf.order().pow(n as u32) > 1000 {
+                    break;
+                }
+                let polys: Vec<Polynomial> = gf.irreducible_polynomials(n).collect();
+                assert_eq!(polys.len() as u128, gf.count_irreducible_polynomials(n));
+                assert!(polys.windows(2).all(|w| w[0] != w[1]));
+            }
+        }
+        assert_eq!(
+            GF::new(2, 1).count_irreducible_polynomials(64),
+            288230376084602880
+        );
+    }
+
+    #[test]
+    fn primitive_counts() {
+        for (p, m) in [(2, 1), (3, 1), (2, 2)] {
+            let gf = GF::new(p, m);
+            for n in 1..=4 {
+                if gf.order().pow(n as u32) > 300 {
+                    break;
+                }
+                let count = gf.primitive_polynomials(n).count() as u128;
+                assert_eq!(count, gf.count_primitive_polynomials(n));
+            }
+        }
+        assert_eq!(GF::new(2, 1).count_primitive_polynomials(8), 16);
+    }
+
+    #[test]
+    fn low_weight_polynomials() {
+        let f2 = GF::new(2, 1);
+        let weight = |f: &Polynomial| f.coef.iter().filter(|c| !c.is_0()).count();
+        for n in 2..=10 {
+            let f = f2.low_weight_primitive(n).unwrap();
+            assert!(f.is_primitive());
+            // a primitive trinomial exists for these degrees except 8
+            assert_eq!(weight(&f), if n == 8 { 5 } else { 3 });
+            // the search returns the first one among all polynomials of that weight
+            let first = f2
+                .primitive_polynomials(n)
+                .filter(|g| weight(g) == weight(&f))
+                .min_by_key(|g| {
+                    let mut e: Vec<usize> = (0..n).filter(|&i| !g.coef[i].is_0()).collect();
+                    e.reverse();
+                    e
+                });
+            assert_eq!(first, Some(f));
+        }
+        // x^2 + 1 is irreducible over F_3
+        let f = GF::new(3, 1).low_weight_irreducible(2).unwrap();
+        assert_eq!(weight(&f), 2);
+        assert!(f.is_irreducible());
+        let f = GF::new(2, 2).low_weight_primitive(3).unwrap();
+        assert!(f.is_primitive());
+    }
+
+    #[test]
+    fn inverse_in_large_field() {
+        // GF(3^20) = F_3[x] / (x^20 + x^5 + 2), far beyond inversion by exponentiation
+        let modulus = Polynomial {
+            coef: [
+                2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            ]
+            .iter()
+            .map(|&c| FiniteField {
+                char: 3,
+                element: Element::PrimeField { element: c },
+            })
+            .collect(),
+        };
+        let gf = GF::with_modulus(modulus);
+        let a = gf.elem(&[1, 2, 0, 1, 1, 0, 2, 0, 0, 1, 0, 0, 0, 2, 1]);
+        assert_eq!(a.clone() * a.inv(), gf.one());
+        // a^(q-1) = 1
+        assert_eq!(a.pow(gf.order() - 1), gf.one());
+        assert_eq!(a.pow(gf.order() - 2), a.inv());
+        // a^(2^128)
+        let squared = (0..128).fold(a.clone(), |b, _| b.clone() * b);
+        assert_eq!(a.pow_big(&[0, 0, 1]), squared);
+    }
+
+    #[test]
+    fn frobenius() {
+        for gf in [GF::new(3, 4), GF::new(5, 3).with_log_tables()] {
+            for i in [1, 7, 20, 50] {
+                let a = gf.elem(&gf.index_to_coef(i));
+                assert_eq!(a.frobenius(), a.pow(gf.char() as u128));
+            }
+        }
+    }
+
+    #[test]
+    fn prime_field() {
+        let gf = GF::new(7, 1);
+        assert!(matches!(
+            gf.elem(&[9]).element,
+            Element::PrimeField { element: 2 }
+        ));
+        assert!(gf.modulus().clone().assign_value(gf.gen()).is_0());
+    }
+
+    #[test]
+    #[should_panic(expected = "needs odd p and n > 1, not GF(2^4)")]
+    fn non_square_in_characteristic_2() {
+        GF::new(2, 4).non_square_coef();
+    }
+
+    #[test]
+    #[should_panic(expected = "not below 2^63")]
+    fn characteristic_above_i64() {
+        // 2^64 - 59 is a prime, but its residues do not fit in i64 coefficients
+        GF::new(18446744073709551557, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "2^128 or more elements")]
+    fn order_above_u128() {
+        // x^3 - 5 over F_p for p = 2^61 - 1, where 5 is not a cube
+        let p = (1u64 << 61) - 1;
+        let modulus = Polynomial {
+            coef: [-5, 0, 0, 1]
+                .iter()
+                .map(|&c| FiniteField {
+                    char: p,
+                    element: Element::PrimeField { element: c },
+                })
+                .collect(),
+        };
+        let gf = GF::with_modulus(modulus);
+        assert_eq!(gf.degree(), 3);
+        gf.order();
+    }
+
+    #[test]
+    #[should_panic(expected = "irreducible")]
+    fn reducible_modulus() {
+        // x^2 + 1 = (x + 1)^2 over F_2
+        let modulus = Polynomial {
+            coef: [1, 0, 1]
+                .iter()
+                .map(|&c| FiniteField {
+                    char: 2,
+                    element: Element::PrimeField { element: c },
+                })
+                .collect(),
+        };
+        GF::with_modulus(modulus);
+    }
+}
@@ -0,0 +1,322 @@
+//! Index calculus for discrete logarithms in F_p^* for primes of roughly 40 to 80 bits.
+
+use crate::bigfp::{BigFp, BigPrimeField};
+use crate::biguint::BigUint;
+use crate::dlog::BSGS_LIMIT;
+use crate::integer::{crt, factorize, is_prime, mul_mod, pow_mod};
+use crate::{Element, Field, FiniteField, Matrix};
+use std::collections::HashMap;
+
+// relations collected beyond the size of the factor base before solving
+const EXTRA_RELATIONS: usize = 10;
+
+/// Discrete logarithms to a generator g of F_p^* by index calculus.
+///
+/// Construction collects relations g^k = product of factor base primes by trial division of
+/// g^k mod p, and solves for the logs of the factor base modulo every prime factor of p - 1 above
+/// the reach of baby-step giant-step, by `Matrix::sweep_method` over that prime field. `log` then
+/// looks for a smooth h g^k and reads off log h, while the small prime power parts of p - 1 are
+/// handled by Pohlig-Hellman.
+///
+/// The factor base is the primes up to `bound`; around 2^10 suits 40-bit primes. Larger primes
+/// want a larger base, and the dense elimination then dominates, growing with its cube.
+/// ```
+/// use galois_field::*;
+/// // p - 1 = 2 * 3^2 * 5 * 7 * 8589934627
+/// let p = 5411658815011;
+/// let ic = IndexCalculus::new(p, 2, 1 << 10);
+/// let h = 1234567890123;
+/// let x = ic.log(h).unwrap();
+/// assert_eq!(FiniteField { char: p as u64, element: Element::PrimeField { element: 2 } }.pow(x),
+///            FiniteField { char: p as u64, element: Element::PrimeField { element: h as i64 } });
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexCalculus {
+    p: u128,
+    g: u128,
+    factor_base: Vec<u128>,
+    // large primes l of p - 1 with the logs of the factor base mod l
+    logs: Vec<(u128, Vec<u128>)>,
+    // prime powers of p - 1 left to Pohlig-Hellman
+    small: Vec<(u128, u32)>,
+}
+
+impl IndexCalculus {
+    /// Precompute the logs of the primes up to `bound` to the base g.
+    ///
+    /// Panics if p is not a prime, g does not generate F_p^*, `bound` < 2, a prime above the
+    /// baby-step giant-step limit divides p - 1 more than once, or the relations determine the log
+    /// of no prime of the factor base.
+    pub fn new(p: u128, g: u128, bound: u128) -> IndexCalculus {
+        assert!(is_prime(p), "{} is not a prime", p);
+        assert!(bound >= 2, "the factor base bound must be at least 2");
+        let g = g % p;
+        let order = p - 1;
+        let factors = factorize(order);
+        assert!(
+            g != 0 && factors.iter().all(|&(r, _)| pow_mod(g, order / r, p) != 1),
+            "{} does not generate the multiplicative group mod {}",
+            g,
+            p
+        );
+        let (large, small): (Vec<_>, Vec<_>) =
+            factors.into_iter().partition(|&(r, _)| r > BSGS_LIMIT);
+        assert!(
+            large.iter().all(|&(_, e)| e == 1),
+            "large prime factors of p - 1 must be simple"
+        );
+        let factor_base: Vec<u128> = (2..=bound.min(p - 1)).filter(|&q| is_prime(q)).collect();
+
+        let mut ic = IndexCalculus {
+            p,
+            g,
+            factor_base,
+            logs: Vec::new(),
+            small,
+        };
+        if large.is_empty() {
+            return ic;
+        }
+        // g^k = product of q^e over the factor base, so k = sum of e log q mod p - 1
+        let m = ic.factor_base.len();
+        let mut relations: Vec<(Vec<u32>, u128)> = Vec::new();
+        let mut target = m + EXTRA_RELATIONS;
+        let (mut k, mut y) = (0, 1);
+        loop {
+            while relations.len() < target {
+                k += 1;
+                y = mul_mod(y, g, p);
+                if let Some(exponents) = ic.factor(y) {
+                    relations.push((exponents, k));
+                }
+            }
+            let solutions: Vec<Vec<Option<u128>>> = large
+                .iter()
+                .map(|&(l, _)| solve_mod(&relations, l))
+                .collect();
+            // primes whose log is determined modulo every large l
+            let determined: Vec<usize> = (0..m)
+                .filter(|&i| solutions.iter().all(|s| s[i].is_some()))
+                .collect();
+            // large primes of the factor base rarely occur, so rather than waiting for all of
+            // them the base shrinks to the determined ones after 3m relations
+            if determined.len() == m || target >= 3 * m {
+                assert!(
+                    !determined.is_empty(),
+                    "no log of the factor base was determined; try a larger bound"
+                );
+                ic.factor_base = determined.iter().map(|&i| ic.factor_base[i]).collect();
+                ic.logs = large
+                    .iter()
+                    .zip(&solutions)
+                    .map(|(&(l, _), s)| (l, determined.iter().map(|&i| s[i].unwrap()).collect()))
+                    .collect();
+                return ic;
+            }
+            target = 3 * m;
+        }
+    }
+
+    /// The primes of the factor base.
+    pub fn factor_base(&self) -> &[u128] {
+        &self.factor_base
+    }
+
+    /// The x in 0..p-1 with g^x = h mod p, or None if h = 0 mod p.
+    pub fn log(&self, h: u128) -> Option<u128> {
+        let (p, order) = (self.p, self.p - 1);
+        let h = h % p;
+        if h == 0 {
+            return None;
+        }
+        let mut x = 0;
+        let mut modulus = 1;
+        for &(r, e) in &self.small {
+            let re = r.pow(e);
+            x = crt(x, modulus, self.small_log(h, r, e), re);
+            modulus *= re;
+        }
+        if !self.logs.is_empty() {
+            // h g^k smooth gives log h = sum of e log q - k
+            let (mut k, mut y) = (0, h);
+            let exponents = loop {
+                if let Some(exponents) = self.factor(y) {
+                    break exponents;
+                }
+                k += 1;
+                y = mul_mod(y, self.g, p);
+            };
+            for (l, logs) in &self.logs {
+                let sum = exponents
+                    .iter()
+                    .zip(logs)
+                    .fold(0, |acc, (&e, &log)| (acc + mul_mod(e as u128, log, *l)) % l);
+                x = crt(x, modulus, (sum + l - k % l) % l, *l);
+                modulus *= l;
+            }
+        }
+        debug_assert_eq!(modulus, order);
+        Some(x)
+    }
+
+    // exponents of y over the factor base, if y factors completely
+    fn factor(&self, mut y: u128) -> Option<Vec<u32>> {
+        let mut exponents = vec![0; self.factor_base.len()];
+        for (e, &q) in exponents.iter_mut().zip(&self.factor_base) {
+            while y.is_multiple_of(q) {
+                y /= q;
+                *e += 1;
+            }
+            if y == 1 {
+                return Some(exponents);
+            }
+        }
+        None
+    }
+
+    // log h mod r^e by Pohlig-Hellman with baby-step giant-step in the subgroup of order r
+    fn small_log(&self, h: u128, r: u128, e: u32) -> u128 {
+        let (p, n) = (self.p, self.p - 1);
+        let gamma = pow_mod(self.g, n / r, p);
+        let m = (r as f64).sqrt().ceil() as u128;
+        let mut baby = HashMap::new();
+        let mut power = 1;
+        for j in 0..m {
+            baby.entry(power).or_insert(j);
+            power = mul_mod(power, gamma, p);
+        }
+        // gamma^(-m)
+        let giant = pow_mod(gamma, r - m % r, p);
+        let g_inv = pow_mod(self.g, n - 1, p);
+        let (mut x, mut r_k) = (0, 1);
+        for _ in 0..e {
+            // (h g^(-x))^(n / r^(k+1)) = gamma^(digit)
+            let mut y = pow_mod(mul_mod(h, pow_mod(g_inv, x, p), p), n / (r_k * r), p);
+            let digit = (0..m)
+                .find_map(|i| {
+                    let found = baby.get(&y).map(|&j| (i * m + j) % r);
+                    y = mul_mod(y, giant, p);
+                    found
+                })
+                .unwrap();
+            x += digit * r_k;
+            r_k = r_k.saturating_mul(r);
+        }
+        x
+    }
+}
+
+// logs of the factor base mod the prime l from relations (exponents, k), by row reducing [E | k]
+// over F_l; a log is None unless the relations determine it
+fn solve_mod(relations: &[(Vec<u32>, u128)], l: u128) -> Vec<Option<u128>> {
+    if l <= i64::MAX as u128 {
+        let c = |x: u128| FiniteField {
+            char: l as u64,
+            element: Element::PrimeField {
+                element: (x % l) as i64,
+            },
+        };
+        let value = |x: &FiniteField| match x.element {
+            Element::PrimeField { element } => element.rem_euclid(l as i64) as u128,
+            Element::GaloisField { .. } => unreachable!(),
+        };
+        solve_with(relations, c, value)
+    } else {
+        let field = BigPrimeField::new(BigUint::from(l));
+        let c = |x: u128| field.elem(BigUint::from(x));
+        let value = |x: &BigFp| {
+            let limbs = x.value().limbs();
+            limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| acc << 64 | limb as u128)
+        };
+        solve_with(relations, c, value)
+    }
+}
+
+fn solve_with<F: Field>(
+    relations: &[(Vec<u32>, u128)],
+    c: impl Fn(u128) -> F,
+    value: impl Fn(&F) -> u128,
+) -> Vec<Option<u128>> {
+    let m = relations[0].0.len();
+    let matrix = Matrix {
+        element: relations
+            .iter()
+            .map(|(exponents, k)| {
+                let row = exponents.iter().map(|&e| c(e as u128));
+                row.chain(std::iter::once(c(*k))).collect()
+            })
+            .collect(),
+    };
+    let reduced = matrix.sweep_method();
+    let mut logs = vec![None; m];
+    // a row with a single nonzero entry among the first m columns fixes that log
+    for row in &reduced.element {
+        let mut nonzero = (0..m).filter(|&j| !row[j].is_zero());
+        if let (Some(j), None) = (nonzero.next(), nonzero.next()) {
+            logs[j] = Some(value(&row[m]));
+        }
+    }
+    logs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_prime() {
+        // p = 2l + 1 with l = 549755814221 prime
+        let p = 1099511628443;
+        let ic = IndexCalculus::new(p, 2, 1 << 10);
+        // the primes up to 2^10 whose logs the relations determine
+        assert!(ic.factor_base().len() > 150);
+        assert!(ic.factor_base().iter().all(|&q| q < 1 << 10 && is_prime(q)));
+        for x in [0, 1, 777, 549755814221, 1099511628441] {
+            assert_eq!(ic.log(pow_mod(2, x, p)), Some(x));
+        }
+        // the factor base primes themselves
+        for &q in &ic.factor_base()[..10] {
+            assert_eq!(pow_mod(2, ic.log(q).unwrap(), p), q);
+        }
+        assert_eq!(ic.log(p), None);
+    }
+
+    #[test]
+    fn small_and_large_factors() {
+        // p - 1 = 2 * 3^2 * 5 * 7 * 8589934627
+        let p = 5411658815011;
+        let ic = IndexCalculus::new(p, 2, 1 << 10);
+        let mut h = 12345;
+        for _ in 0..5 {
+            h = mul_mod(h, 6364136223846793005, p);
+            let x = ic.log(h).unwrap();
+            assert!(x < p - 1);
+            assert_eq!(pow_mod(2, x, p), h);
+        }
+        // agrees with Pohlig-Hellman and rho
+        let fp = |x: u128| FiniteField {
+            char: p as u64,
+            element: Element::PrimeField { element: x as i64 },
+        };
+        assert_eq!(fp(h).discrete_log(&fp(2)), ic.log(h));
+    }
+
+    #[test]
+    #[should_panic(expected = "bound must be at least 2")]
+    fn empty_factor_base() {
+        IndexCalculus::new(1099511628443, 2, 1);
+    }
+
+    #[test]
+    fn linear_algebra_over_big_prime() {
+        // relations g^k = 2^a 3^b for g = 2^3 3^5 mod a prime l above 2^63,
+        // where log 2 = 10 and log 3 = 20, so k = 10a + 20b
+        let l = (1u128 << 64) - 59;
+        let relations = vec![(vec![1, 2], 50), (vec![3, 1], 50), (vec![2, 2], 60)];
+        assert_eq!(solve_mod(&relations, l), vec![Some(10), Some(20)]);
+        assert_eq!(solve_mod(&relations[..1], l), vec![None, None]);
+    }
+}
@@ -0,0 +1,475 @@
+//! Integer arithmetic: primality tests and factorization.
+
+// Miller-Rabin with the first 13 prime bases is deterministic below this (Sorenson and Webster)
+const MILLER_RABIN_BOUND: u128 = 3_317_044_064_679_887_385_961_981;
+
+/// Determine if n is a prime.
+///
+/// Below 3.3 * 10^24 this is a deterministic Miller-Rabin test. Above it, it is the Baillie-PSW
+/// test (Miller-Rabin to base 2 and a strong Lucas test), which is probabilistic: no composite
+/// passing it is known, but none has been proven not to exist.
+/// ```
+/// use galois_field::*;
+/// assert!(is_prime(65537));
+/// assert!(is_prime((1 << 127) - 1));
+/// assert!(!is_prime(561));
+/// ```
+pub fn is_prime(n: u128) -> bool {
+    if n <= u64::MAX as u128 {
+        return is_prime_u64(n as u64);
+    }
+    let bases: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+    if bases.iter().any(|&a| n.is_multiple_of(a)) {
+        return false;
+    }
+    if n < MILLER_RABIN_BOUND {
+        bases.iter().all(|&a| is_strong_probable_prime(n, a))
+    } else {
+        is_strong_probable_prime(n, 2) && is_strong_lucas_probable_prime(n)
+    }
+}
+
+// Miller-Rabin round: n - 1 = d 2^s with d odd, and a^d = 1 or a^(d 2^r) = -1 for some r < s
+fn is_strong_probable_prime(n: u128, a: u128) -> bool {
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    let mut x = pow_mod(a, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+// Strong Lucas test for odd n > 41 with Selfridge's parameters: the first D in 5, -7, 9, -11, ...
+// with (D/n) = -1, P = 1 and Q = (1 - D)/4. n + 1 = d 2^s with d odd, and U_d = 0 or
+// V_(d 2^r) = 0 for some r < s.
+fn is_strong_lucas_probable_prime(n: u128) -> bool {
+    // no such D exists for a square
+    if n.isqrt().pow(2) == n {
+        return false;
+    }
+    // D = sign * abs, kept as a residue mod n
+    let mut abs = 5u128;
+    let mut negative = false;
+    let d = loop {
+        let d = if negative { n - abs } else { abs };
+        match jacobi(d, n) {
+            -1 => break d,
+            0 => return false,
+            _ => {}
+        }
+        abs += 2;
+        negative = !negative;
+    };
+    // Q = (1 - D)/4 mod n, where 4 is invertible as n is odd
+    let q = mul_mod(sub_mod(1, d, n), inv_mod(4, n).unwrap(), n);
+    let half = |x: u128| {
+        if x.is_multiple_of(2) {
+            x / 2
+        } else {
+            x / 2 + n / 2 + 1
+        }
+    };
+
+    let mut e = n + 1;
+    let mut s = 0;
+    while e.is_multiple_of(2) {
+        e /= 2;
+        s += 1;
+    }
+    // U_k, V_k and Q^k for k the leading bits of e, starting from k = 1 with P = 1
+    let (mut u, mut v, mut qk) = (1u128, 1u128, q);
+    for i in (0..127 - e.leading_zeros()).rev() {
+        // k -> 2k
+        u = mul_mod(u, v, n);
+        v = sub_mod(mul_mod(v, v, n), add_mod(qk, qk, n), n);
+        qk = mul_mod(qk, qk, n);
+        if (e >> i) & 1 == 1 {
+            // k -> k + 1
+            (u, v) = (
+                half(add_mod(u, v, n)),
+                half(add_mod(mul_mod(d, u, n), v, n)),
+            );
+            qk = mul_mod(qk, q, n);
+        }
+    }
+    if u == 0 || v == 0 {
+        return true;
+    }
+    for _ in 1..s {
+        v = sub_mod(mul_mod(v, v, n), add_mod(qk, qk, n), n);
+        qk = mul_mod(qk, qk, n);
+        if v == 0 {
+            return true;
+        }
+    }
+    false
+}
+
+// Jacobi symbol (a/n) for odd n
+pub(crate) fn jacobi(mut a: u128, mut n: u128) -> i8 {
+    a %= n;
+    let mut result = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        (a, n) = (n, a);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Deterministic Miller-Rabin test, valid for every u64.
+pub(crate) const fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let bases: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    let mut i = 0;
+    while i < bases.len() {
+        if n == bases[i] {
+            return true;
+        }
+        if n.is_multiple_of(bases[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    let mut i = 0;
+    'witness: while i < bases.len() {
+        let mut x = pow_mod_u64(bases[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod_u64(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+const fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+const fn pow_mod_u64(mut base: u64, mut e: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod_u64(result, base, m);
+        }
+        base = mul_mod_u64(base, base, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// a * b mod m without overflow.
+pub(crate) fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (a, b) = (a % m, b % m);
+    if m <= u64::MAX as u128 {
+        return a * b % m;
+    }
+    // double-and-add, every intermediate stays below 2m
+    let mut result: u128 = 0;
+    let mut a = a;
+    let mut b = b;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn sub_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// base^e mod m.
+pub(crate) fn pow_mod(base: u128, mut e: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// a^(-1) mod m, or None if a and m are not coprime.
+pub(crate) fn inv_mod(a: u128, m: u128) -> Option<u128> {
+    // r0 = t0 * a, r1 = t1 * a (mod m)
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut t0, mut t1) = (0, 1 % m);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        let qt = mul_mod(q, t1, m);
+        let t = if t0 >= qt { t0 - qt } else { m - (qt - t0) };
+        (t0, t1) = (t1, t);
+    }
+    (r0 == 1).then_some(t0)
+}
+
+/// The x mod mn with x = a mod m and x = b mod n, for coprime m and n with mn < 2^128.
+pub(crate) fn crt(a: u128, m: u128, b: u128, n: u128) -> u128 {
+    let a_mod_n = a % n;
+    let diff = if b >= a_mod_n {
+        b - a_mod_n
+    } else {
+        n - (a_mod_n - b)
+    };
+    a + m * mul_mod(diff, inv_mod(m % n, n).unwrap(), n)
+}
+
+pub(crate) fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Möbius function: 0 if n has a square factor, otherwise (-1)^(number of prime factors).
+/// ```
+/// use galois_field::*;
+/// assert_eq!(mobius(1), 1);
+/// assert_eq!(mobius(30), -1);
+/// assert_eq!(mobius(12), 0);
+/// ```
+pub fn mobius(n: u128) -> i8 {
+    let factors = factorize(n);
+    if factors.iter().any(|&(_, e)| e > 1) {
+        0
+    } else if factors.len().is_multiple_of(2) {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Euler's totient function, the number of 1 <= k <= n coprime to n.
+/// ```
+/// use galois_field::*;
+/// assert_eq!(euler_phi(1), 1);
+/// assert_eq!(euler_phi(255), 128);
+/// ```
+pub fn euler_phi(n: u128) -> u128 {
+    factorize(n)
+        .iter()
+        .fold(n, |acc, &(p, _)| acc / p * (p - 1))
+}
+
+/// Divisors of n in ascending order.
+pub(crate) fn divisors(n: u128) -> Vec<u128> {
+    let mut result = vec![1];
+    for (p, e) in factorize(n) {
+        let mut powers = Vec::with_capacity(result.len() * e as usize);
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            powers.extend(result.iter().map(|d| d * pk));
+        }
+        result.extend(powers);
+    }
+    result.sort();
+    result
+}
+
+/// Prime factorization of n as (prime, exponent) pairs in ascending order; empty for n = 1.
+///
+/// Small factors are found by trial division and the rest by Pollard's rho method.
+/// ```
+/// use galois_field::*;
+/// // 2^32 - 1
+/// assert_eq!(factorize(4294967295), vec![(3, 1), (5, 1), (17, 1), (257, 1), (65537, 1)]);
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// ```
+pub fn factorize(n: u128) -> Vec<(u128, u32)> {
+    assert!(n > 0, "0 has no factorization");
+    let mut primes: Vec<u128> = Vec::new();
+    let mut n = n;
+    for d in (2..1000).filter(|&d| is_prime_u64(d as u64)) {
+        while n.is_multiple_of(d) {
+            primes.push(d);
+            n /= d;
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let d = pollard_rho(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+    primes.sort();
+
+    let mut result: Vec<(u128, u32)> = Vec::new();
+    for p in primes {
+        match result.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => result.push((p, 1)),
+        }
+    }
+    result
+}
+
+// nontrivial factor of a composite n without small prime factors (Brent's variant)
+fn pollard_rho(n: u128) -> u128 {
+    for c in 1.. {
+        let f = |x: u128| add_mod(mul_mod(x, x, n), c, n);
+        let (mut x, mut y, mut ys) = (2u128, 2u128, 2u128);
+        let mut q: u128 = 1;
+        let mut g: u128 = 1;
+        let mut r: u64 = 1;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..(r - k).min(128) {
+                    y = f(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += 128;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // the batch overshot, retrace one step at a time
+            loop {
+                ys = f(ys);
+                g = gcd(x.abs_diff(ys), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorize_products() {
+        for n in [1u128, 2, 97, 1 << 40, 600851475143, 18446744073709551615] {
+            let product: u128 = factorize(n).iter().map(|&(p, e)| p.pow(e)).product();
+            assert_eq!(product, n);
+            assert!(factorize(n).iter().all(|&(p, _)| is_prime(p)));
+        }
+        // (2^61 - 1) (2^31 - 1) and 3^80 - 1 (> 2^126)
+        let n = ((1u128 << 61) - 1) * ((1 << 31) - 1);
+        assert_eq!(factorize(n), vec![((1 << 31) - 1, 1), ((1 << 61) - 1, 1)]);
+        let n = 3u128.pow(80) - 1;
+        let product: u128 = factorize(n).iter().map(|&(p, e)| p.pow(e)).product();
+        assert_eq!(product, n);
+    }
+
+    #[test]
+    fn mobius_and_divisors() {
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        // sum of mu(d) over the divisors d of n is 0 for n > 1
+        for n in 2..200 {
+            let sum: i64 = divisors(n).iter().map(|&d| mobius(d) as i64).sum();
+            assert_eq!(sum, 0);
+        }
+    }
+
+    #[test]
+    fn primes_above_u64() {
+        assert!(is_prime((1 << 89) - 1));
+        assert!(!is_prime(((1u128 << 61) - 1).pow(2)));
+        assert!(is_prime_u64(18446744073709551557));
+        // 2^89 - 1 and up are above the deterministic Miller-Rabin bound
+        for e in [89, 107, 127] {
+            assert!(is_prime((1 << e) - 1));
+        }
+        assert!(!is_prime((1 << 101) - 1));
+        assert!(!is_prime(((1u128 << 61) - 1) * 18446744073709551557));
+    }
+
+    #[test]
+    fn strong_lucas_test() {
+        // agrees with primality on odd numbers, except for the strong Lucas pseudoprimes
+        let pseudoprimes = [
+            5459, 5777, 10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519,
+        ];
+        for n in (43..60000u128).step_by(2) {
+            let expected = is_prime_u64(n as u64) || pseudoprimes.contains(&n);
+            assert_eq!(is_strong_lucas_probable_prime(n), expected, "{}", n);
+        }
+        // none of them is a strong probable prime to base 2
+        assert!(pseudoprimes
+            .iter()
+            .all(|&n| !is_strong_probable_prime(n, 2)));
+    }
+}
@@ -5,14 +5,15 @@
 //! # Quick Start
 //! ## Case 1: Prime Field
 //! ```
-//! let char: u32 = 5;
+//! use galois_field::*;
+//! let char: u64 = 5;
 //! let x:FiniteField = FiniteField{
-//! 	char: char,
-//! 	element:Element::PrimeField{element:0} // 0 in F_5
+//!     char,
+//!     element:Element::PrimeField{element:0} // 0 in F_5
 //! };
 //! let y:FiniteField = FiniteField{
-//! 	char: char,
-//! 	element:Element::PrimeField{element:1} // 1 in F_5
+//!     char,
+//!     element:Element::PrimeField{element:1} // 1 in F_5
 //! };
 //! println!("x + y = {:?}", (x.clone() + y.clone()).element); // ->1
 //! println!("x - y = {:?}", (x.clone() - y.clone()).element); // -> 4
@@ -30,22 +31,17 @@
 //! ```
 //! use galois_field::*;
 //! fn main(){
-//! 	// consider GF(2^4)
-//! 	let char: u32 = 2;
-//! 	let n = 4;
-//! 	let primitive_polynomial = Polynomial::get_primitive_polynomial(char, n);
-//! 	let x:FiniteField = FiniteField{
-//!  		char: char,
-//!  		element:Element::GaloisField{element:vec![0,1],primitive_polynomial:primitive_polynomial.clone()} // i.e. [0,1] = x -> 2 over GF(2^4)
-//! 	};
-//! 	let y:FiniteField = FiniteField{
-//!  		char: char,
-//!  		element:Element::GaloisField{element:vec![0,0,1,1],primitive_polynomial:primitive_polynomial.clone()} // i.e. [0,0,1,1] = x^3 + x^2 -> 12 over GF(2^4)
-//! 	};
-//! 	println!("x + y = {:?}", (x.clone() + y.clone()).element);
-//! 	println!("x - y = {:?}", (x.clone() - y.clone()).element);
-//! 	println!("x * y = {:?}", (x.clone() * y.clone()).element);
-//! 	println!("x / y = {:?}", (x.clone() / y.clone()).element);
+//!     // consider GF(2^4), defined by the primitive polynomial x^4 + x + 1
+//!     let gf = GF::new(2, 4);
+//!     let x:FiniteField = gf.elem(&[0,1]); // i.e. [0,1] = x -> 2 over GF(2^4)
+//!     let y:FiniteField = FiniteField{
+//!         char: 2,
+//!         element:Element::GaloisField{element:vec![0,0,1,1],field:gf.clone()} // i.e. [0,0,1,1] = x^3 + x^2 -> 12 over GF(2^4)
+//!     };
+//!     println!("x + y = {:?}", (x.clone() + y.clone()).element);
+//!     println!("x - y = {:?}", (x.clone() - y.clone()).element);
+//!     println!("x * y = {:?}", (x.clone() * y.clone()).element);
+//!     println!("x / y = {:?}", (x.clone() / y.clone()).element);
 //! }
 //! ```
 //!
@@ -53,24 +49,21 @@
 //! ```
 //! use galois_field::*;
 //! // This is a test of the elementary F_p, Galois GF(p^n), polynomial quadrature.
-//! 	// character
-//! let char: u32 = 2;
+//! // character
+//! let char: u64 = 2;
 //! let element0:FiniteField = FiniteField{
-//! 	char: char,
-//! 	element:Element::PrimeField{element:0} // 0 in F_5
+//!     char,
+//!     element:Element::PrimeField{element:0} // 0 in F_5
 //! };
 //! let element1:FiniteField = FiniteField{
-//! 	char: char,
-//! 	element:Element::PrimeField{element:1} // 1 in F_5
+//!     char,
+//!     element:Element::PrimeField{element:1} // 1 in F_5
 //! };
-//! // operations between GaloisField elements
-//! // we need primitive_polynomial
-//! let primitive_polynomial = Polynomial::get_primitive_polynomial(char,4);
 //! let f: Polynomial = Polynomial {
 //!     coef: vec![element1.clone(),element0.clone(),element0.clone(),element0.clone(),element1.clone()]
 //! };
 //! let g: Polynomial = Polynomial {
-//! 	coef: vec![element1.clone(),element0.clone(),element0.clone(),element1.clone(),element1.clone()]
+//!     coef: vec![element1.clone(),element0.clone(),element0.clone(),element1.clone(),element1.clone()]
 //! };
 //! println!("f + g = {:?}", (f.clone()+g.clone()).coef);
 //! println!("f - g = {:?}", (f.clone()-g.clone()).coef);
@@ -85,35 +78,143 @@
 //! use galois_field::*;
 //! let char = 3;
 //! let element0: FiniteField = FiniteField {
-//! 	char: char,
-//! 	element: Element::PrimeField { element: 0 },
+//!     char,
+//!     element: Element::PrimeField { element: 0 },
 //! };
 //! let element1: FiniteField = FiniteField {
-//! 	char: char,
-//! 	element: Element::PrimeField { element: 1 },
+//!     char,
+//!     element: Element::PrimeField { element: 1 },
 //! };
 //! let element2: FiniteField = FiniteField {
-//! 	char: char,
-//! 	element: Element::PrimeField { element: 2 },
+//!     char,
+//!     element: Element::PrimeField { element: 2 },
 //! };
-//! let mut matrix_element:Vec<Vec<FiniteField>> = vec![
-//! 	vec![element0.clone(),element1.clone(), element0.clone()],
-//! 	vec![element2.clone(),element2.clone(), element1.clone()],
-//! 	vec![element1.clone(),element0.clone(), element1.clone()]
+//! let matrix_element:Vec<Vec<FiniteField>> = vec![
+//!     vec![element0.clone(),element1.clone(), element0.clone()],
+//!     vec![element2.clone(),element2.clone(), element1.clone()],
+//!     vec![element1.clone(),element0.clone(), element1.clone()]
 //! ];
-//! let mut m = Matrix{
-//! 	element: matrix_element,
+//! let m = Matrix{
+//!     element: matrix_element,
 //! };
 //! println!("m+m = {:?}", m.clone()+m.clone());
 //! println!("m*m = {:?}", m.clone()*m.clone());
-//! let mut sweep_matrix = m.sweep_method();
+//! let sweep_matrix = m.sweep_method();
 //! println!("{:?}", sweep_matrix);
 //! ```
+//! ## Case 6: Your own field type
+//! `Polynomial` and `Matrix` are generic over the [`Field`] trait, so any type implementing it
+//! can reuse polynomial division, `gcd`, `assign_value` and `sweep_method`.
+//! `FiniteField` is the default coefficient type, so `Polynomial` means `Polynomial<FiniteField>`.
+//!
+//! ## Case 7: Prime field fixed at compile time
+//! ```
+//! use galois_field::*;
+//! let f: Polynomial<Fp<5>> = Polynomial {
+//!     coef: vec![Fp::new(4), Fp::new(0), Fp::new(1)], // x^2 + 4 over F_5
+//! };
+//! let x = Fp::<5>::new(2);
+//! println!("f(2) = {}", f.clone().assign_value(x)); // -> 3
+//! ```
 
+mod bigfp;
+mod biguint;
+mod binary;
+mod conway;
+mod dlog;
+mod fp;
+mod gf;
+mod index_calculus;
+mod integer;
+mod log_table;
+mod montgomery;
+mod order;
+mod quadratic;
+mod sqrt;
+mod subfield;
+#[cfg(test)]
+mod testing;
+mod tower;
+mod trace;
+
+pub use bigfp::{BigFp, BigPrimeField};
+pub use biguint::BigUint;
+pub use binary::{BinaryField, GF2n};
+pub use fp::Fp;
+pub use gf::GF;
+pub use index_calculus::IndexCalculus;
+pub use integer::{euler_phi, factorize, is_prime, mobius};
+pub use montgomery::{MontFp, MontModulus};
+pub use subfield::{Embedding, Isomorphism};
+pub use tower::{ExtElement, ExtensionField};
+
+use std::fmt;
 use std::ops;
 // type of number , ex: i32
 type NumType = i64;
 
+/// Common interface of the fields used as coefficients of `Polynomial` and entries of `Matrix`.
+///
+/// Constants are obtained from an existing element (`x.zero()`, `x.one()`),
+/// because a field such as GF(p^n) is only known at runtime.
+pub trait Field:
+    Clone
+    + fmt::Debug
+    + PartialEq
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Neg<Output = Self>
+{
+    /// 0 of the field that `self` belongs to.
+    fn zero(&self) -> Self;
+    /// 1 of the field that `self` belongs to.
+    fn one(&self) -> Self;
+    /// Multiplicative inverse. Panics if `self` is 0.
+    fn inv(&self) -> Self;
+    /// Characteristic p of the field.
+    fn characteristic(&self) -> u64;
+    /// Number of elements q = p^n of the field.
+    fn order(&self) -> u128;
+    /// Characteristic as little-endian 64-bit limbs, for fields where it does not fit in u64.
+    fn characteristic_big(&self) -> Vec<u64> {
+        vec![self.characteristic()]
+    }
+    /// Number of elements as little-endian 64-bit limbs.
+    fn order_big(&self) -> Vec<u64> {
+        let q = self.order();
+        vec![q as u64, (q >> 64) as u64]
+    }
+
+    /// Determine if the element is 0.
+    fn is_zero(&self) -> bool {
+        *self == self.zero()
+    }
+    /// Determine if the element is 1.
+    fn is_one(&self) -> bool {
+        *self == self.one()
+    }
+    /// self^e by square-and-multiply.
+    fn pow(&self, e: u128) -> Self {
+        self.pow_big(&[e as u64, (e >> 64) as u64])
+    }
+    /// self^e for an exponent given as little-endian 64-bit limbs.
+    fn pow_big(&self, e: &[u64]) -> Self {
+        let mut result = self.one();
+        for bit in exponent_bits(e) {
+            result = result.clone() * result;
+            if bit {
+                result = result * self.clone();
+            }
+        }
+        result
+    }
+    /// Frobenius map x -> x^p.
+    fn frobenius(&self) -> Self {
+        self.pow_big(&self.characteristic_big())
+    }
+}
+
 /// Element of finite field.
 /// enum Element has two variants: PrimeField and GaloisField.
 /// ## PrimeField
@@ -125,254 +226,305 @@ type NumType = i64;
 /// GaloisField is a field that has prime power as its characteristic.
 ///
 /// Example: GF(2^2), GF(5^3), ...
+///
+/// The coefficients are kept in ascending order, and `field` is a cheap handle to the shared modulus.
 #[derive(Debug, Clone)]
 pub enum Element {
-    PrimeField {
-        element: NumType,
-    },
-    GaloisField {
-        element: Vec<NumType>,
-        primitive_polynomial: Polynomial,
-    },
+    PrimeField { element: NumType },
+    GaloisField { element: Vec<NumType>, field: GF },
 }
 
-/// Polynomial over FiniteField.
+/// Polynomial over a field.
 ///
-/// Either prime field F_p or Galois field GF(p^n) can be used as coefficients
+/// Either prime field F_p or Galois field GF(p^n) can be used as coefficients,
+/// as well as any other type implementing [`Field`].
 #[derive(Debug, Clone)]
-pub struct Polynomial {
-    pub coef: Vec<FiniteField>,
+pub struct Polynomial<F = FiniteField> {
+    pub coef: Vec<F>,
 }
 
 /// FiniteField
+///
+/// Coefficients are stored as i64, so the characteristic must be a prime below 2^63;
+/// arithmetic on elements with a larger `char` panics.
 #[derive(Debug, Clone)]
 pub struct FiniteField {
-    pub char: u32,
+    pub char: u64,
     pub element: Element,
 }
 
-/// Matrix over FiniteField.
+/// Matrix over a field.
 #[derive(Debug, Clone)]
-pub struct Matrix {
-    pub element: Vec<Vec<FiniteField>>,
+pub struct Matrix<F = FiniteField> {
+    pub element: Vec<Vec<F>>,
 }
 
-impl Matrix {
+impl Matrix<FiniteField> {
     /// Extract only the numbers to make the matrix easier to read with println.
     pub fn matrix_visualize(matrix: Matrix) -> Vec<Vec<i64>> {
         let mut h_num: Vec<Vec<i64>> = Vec::new();
-        for i in 0..matrix.element.len() {
+        for row in &matrix.element {
             let mut tmp: Vec<i64> = Vec::new();
-            for j in 0..matrix.element[0].len() {
-                if let Element::PrimeField { element: e } =
-                    matrix.element[i as usize][j as usize].element
-                {
-                    tmp.push(e as i64);
+            for x in row {
+                if let Element::PrimeField { element: e } = x.element {
+                    tmp.push(e);
                 }
             }
             h_num.push(tmp);
         }
         h_num
     }
+}
 
+impl<F: Field> Matrix<F> {
     /// Perform a sweep method (or Gauss-Jordan elimination) on the matrix to get a row-staircase form
-    pub fn sweep_method(&self) -> Matrix {
+    ///
+    /// Columns without a pivot are skipped, so singular and non-square matrices are reduced as far as possible.
+    pub fn sweep_method(&self) -> Matrix<F> {
         let n = self.element.len();
         let m = self.element[0].len();
         let mut matrix = self.clone();
-		
-
-		for i in 0..n {
-			// if 0, swap
-			for j in i..n {
-				if !matrix.element[i][i].is_0() {
-					break;
-				} else {
-					(matrix.element[i], matrix.element[j]) =
-						(matrix.element[j].clone(), matrix.element[i].clone());
-				}
-			}
-
-			// to1
-			let head = matrix.element[i][i].clone();
-			for j in 0..m {
-				matrix.element[i][j] = matrix.element[i][j].clone() / head.clone();
-			}
-
-			let mut h_xi: Vec<FiniteField> = Vec::new();
-			for k in 0..n {
-				h_xi.push(matrix.element[k][i].clone());
-			}
-
-			// sub to 0
-			for j in 0..m {
-				let h_ij = matrix.element[i][j].clone();
-				for k in 0..n {
-					if i == k {
-						continue;
-					}
-					let h_kj = matrix.element[k][j].clone();
-					let h_ki = h_xi[k].clone();
-					matrix.element[k][j] = h_kj - (h_ki * h_ij.clone());
-				}
-			}
-		}
+
+        let mut i = 0;
+        for col in 0..m {
+            if i == n {
+                break;
+            }
+            // if 0, swap
+            let pivot = match (i..n).find(|&j| !matrix.element[j][col].is_zero()) {
+                Some(j) => j,
+                None => continue,
+            };
+            matrix.element.swap(i, pivot);
+
+            // to1
+            let head_inv = matrix.element[i][col].inv();
+            for j in 0..m {
+                matrix.element[i][j] = matrix.element[i][j].clone() * head_inv.clone();
+            }
+
+            // sub to 0
+            for k in 0..n {
+                if i == k || matrix.element[k][col].is_zero() {
+                    continue;
+                }
+                let h_kc = matrix.element[k][col].clone();
+                for j in 0..m {
+                    let h_ij = matrix.element[i][j].clone();
+                    matrix.element[k][j] = matrix.element[k][j].clone() - (h_kc.clone() * h_ij);
+                }
+            }
+            i += 1;
+        }
         matrix
     }
 }
 
-impl Polynomial {
+impl Polynomial<FiniteField> {
     /// get primitive polynomial of GF(q^n)
     /// examples: when char = 2, n = 2, return x^2 + x + 1
     ///                  when char = 2, n = 4, return x^4 + x + 1
-    pub fn get_primitive_polynomial(char: u32, n: NumType) -> Polynomial {
+    pub fn get_primitive_polynomial(char: u64, n: NumType) -> Polynomial {
         let mut answer: Polynomial = Polynomial { coef: Vec::new() };
+        let count = (char as u128)
+            .checked_pow(n as u32)
+            .expect("char^n must fit in u128");
 
-        for i in 0..(char.pow(n as u32)) {
+        for i in 0..count {
             // f :nth order monic polynomial on F_p
-            let mut f_vec: Vec<NumType> = change_base_from10_to_n(i as NumType, char as NumType);
-            for _ in 0..(n as usize) - f_vec.len() + 1 {
-                f_vec.push(0);
-            }
-            f_vec.pop();
+            let mut f_vec: Vec<NumType> = change_base_from10_to_n(i, char as u128);
+            f_vec.resize(n as usize, 0);
             f_vec.push(1);
 
             // Vec<NumType> -> Vec<FiniteField>
-            let mut f_vec_ff: Vec<FiniteField> = Vec::new();
-            for j in 0..f_vec.len() {
-                f_vec_ff.push(FiniteField {
-                    char: char,
-                    element: Element::PrimeField { element: f_vec[j] },
-                });
-            }
+            let f_vec_ff: Vec<FiniteField> = f_vec
+                .iter()
+                .map(|&e| FiniteField {
+                    char,
+                    element: Element::PrimeField { element: e },
+                })
+                .collect();
 
             let f: Polynomial = Polynomial { coef: f_vec_ff };
 
-            let mut end_flag: bool = true;
-
-            // g = x
-            let g_vec: Vec<FiniteField> = vec![
-                FiniteField {
-                    char: char,
-                    element: Element::PrimeField { element: 0 },
-                },
-                FiniteField {
-                    char: char,
-                    element: Element::PrimeField { element: 1 },
-                },
-            ];
-            let mut g: Polynomial = Polynomial { coef: g_vec };
-
-            for _ in 0..(n / 2) {
-                let g_temp: Polynomial = g.clone();
-
-                // g^p
-                for _ in 0..char - 1 {
-                    g = g * g_temp.clone();
-                }
-
-                // g = g^p mod f
-                g = g % f.clone();
-
-                let mut g_2 = g.clone();
-                // g^p-1 mod f
-                if g_2.coef.len() == 0 {
-                    g_2.coef.push(FiniteField {
-                        char: char,
-                        element: Element::PrimeField { element: 0 },
-                    });
-                    g_2.coef.push(FiniteField {
-                        char: char,
-                        element: Element::PrimeField {
-                            element: (char - 1) as NumType,
-                        },
-                    });
-                } else if g_2.coef.len() == 1 {
-                    g_2.coef.push(FiniteField {
-                        char: char,
-                        element: Element::PrimeField {
-                            element: (char - 1) as NumType,
-                        },
-                    });
-                } else {
-                    g_2.coef[1] = g_2.coef[1].clone() - g_2.coef[0].get_1();
-                }
-                g_2 = g_2.adjust_func();
-
-                let h = Polynomial::gcd(&f, g_2);
-                if !(h.coef.len() <= 1 && h.coef[0].is_1()) {
-                    end_flag = false;
-                    break;
-                }
-            }
-
-            if end_flag == true {
+            if f.is_primitive() {
                 answer = f;
                 break;
             }
         }
         answer
     }
+}
 
+impl<F: Field> Polynomial<F> {
     /// Assign a value to the polynomial.
     /// The coefficients are in ascending order.
     /// Example: x^2 + 2x + 3 -> [3, 2, 1]
-    pub fn assign_value(&mut self, value: FiniteField) -> FiniteField {
+    pub fn assign_value(&mut self, value: F) -> F {
         // assign value to polynomial
         // example: f.coef = [0,1,2] i.e. f(x) = 2x^2 + x + 0, then
         // f.assign_value(2) = 2*2^2 + 2*1 + 2*0 = 8 = 3 (mod 5)
-        let mut result: FiniteField = FiniteField {
-            char: self.coef[0].char,
-            element: self.coef[0].element.clone(),
-        };
+        let mut result: F = self.coef[0].clone();
 
         let value_origin = value.clone();
         let mut value = value;
-        for i in 0..self.coef.len() {
-            if i == 0 {
-                result = self.coef[i].clone();
-            } else {
-                result = result + (self.coef[i].clone() * value.clone());
-                value = value * value_origin.clone();
+        for c in self.coef.iter().skip(1) {
+            result = result + (c.clone() * value.clone());
+            value = value * value_origin.clone();
+        }
+        result
+    }
+
+    /// self^e by square-and-multiply.
+    pub fn pow(&self, e: u128) -> Polynomial<F> {
+        let mut result = Polynomial {
+            coef: vec![self.coef[0].one()],
+        };
+        for bit in exponent_bits(&[e as u64, (e >> 64) as u64]) {
+            result = result.clone() * result;
+            if bit {
+                result = result * self.clone();
+            }
+        }
+        result
+    }
+
+    /// self^e mod modulus by square-and-multiply, reducing after every step.
+    pub fn pow_mod(&self, e: u128, modulus: &Polynomial<F>) -> Polynomial<F> {
+        self.pow_mod_big(&[e as u64, (e >> 64) as u64], modulus)
+    }
+
+    /// self^e mod modulus for an exponent given as little-endian 64-bit limbs.
+    pub fn pow_mod_big(&self, e: &[u64], modulus: &Polynomial<F>) -> Polynomial<F> {
+        let base = self.clone() % modulus.clone();
+        let mut result = Polynomial {
+            coef: vec![self.coef[0].one()],
+        } % modulus.clone();
+        for bit in exponent_bits(e) {
+            result = (result.clone() * result) % modulus.clone();
+            if bit {
+                result = (result * base.clone()) % modulus.clone();
             }
         }
         result
     }
 
-    fn adjust_func(&mut self) -> Polynomial {
+    /// Determine if the polynomial is irreducible, by Rabin's test.
+    /// f of degree n over F_q is irreducible iff x^(q^n) = x mod f
+    /// and gcd(f, x^(q^(n/r)) - x) = 1 for every prime r dividing n.
+    /// ```
+    /// use galois_field::*;
+    /// let f = |c: &[u64]| Polynomial { coef: c.iter().map(|&x| Fp::<3>::new(x)).collect() };
+    /// assert!(f(&[1, 0, 1]).is_irreducible());
+    /// // x^2 + 2 = (x + 1)(x + 2)
+    /// assert!(!f(&[2, 0, 1]).is_irreducible());
+    /// ```
+    pub fn is_irreducible(&self) -> bool {
+        let f = self.clone().adjust_func();
+        let n = f.coef.len() - 1;
+        if n == 0 {
+            return false;
+        }
+        let q = f.coef[0].order_big();
+        let x = Polynomial {
+            coef: vec![f.coef[0].zero(), f.coef[0].one()],
+        };
+        // x^(q^k) mod f for k = 0, ..., n
+        let mut powers = vec![x.clone() % f.clone()];
+        for k in 0..n {
+            powers.push(powers[k].pow_mod_big(&q, &f));
+        }
+        if powers[n] != powers[0] {
+            return false;
+        }
+        factorize(n as u128).iter().all(|&(r, _)| {
+            // the gcd is a nonzero constant, not necessarily 1
+            let h = powers[n / r as usize].clone() - x.clone();
+            f.gcd(h).coef.len() == 1
+        })
+    }
+
+    /// Determine if the polynomial is primitive, i.e. x generates the multiplicative group of F_q[x]/(f).
+    /// Checks x^(q^n-1) = 1 and x^((q^n-1)/r) != 1 mod f for every prime r dividing q^n-1,
+    /// which also implies that f is irreducible.
+    /// ```
+    /// use galois_field::*;
+    /// let f = |c: &[u64]| Polynomial { coef: c.iter().map(|&x| Fp::<2>::new(x)).collect() };
+    /// // x^4 + x + 1 is primitive, x^4 + x^3 + x^2 + x + 1 is irreducible but x^5 = 1
+    /// assert!(f(&[1, 1, 0, 0, 1]).is_primitive());
+    /// assert!(!f(&[1, 1, 1, 1, 1]).is_primitive());
+    /// ```
+    pub fn is_primitive(&self) -> bool {
+        let f = self.clone().adjust_func();
+        let n = f.coef.len() - 1;
+        if n == 0 {
+            return false;
+        }
+        let q = f.coef[0].order();
+        let order = u32::try_from(n)
+            .ok()
+            .and_then(|n| q.checked_pow(n))
+            .expect("q^n must fit in u128")
+            - 1;
+        let (zero, one) = (f.coef[0].zero(), f.coef[0].one());
+        let x = Polynomial {
+            coef: vec![zero, one.clone()],
+        };
+        let one = Polynomial { coef: vec![one] };
+        x.pow_mod(order, &f) == one
+            && factorize(order)
+                .iter()
+                .all(|&(r, _)| x.pow_mod(order / r, &f) != one)
+    }
+
+    /// f(x)^p mod modulus = sum of c_i^p (x^p)^i, where p is the characteristic.
+    ///
+    /// x^p is taken mod modulus by `pow_mod_big`, so the degree stays below that of the modulus
+    /// however large p is.
+    pub fn frobenius_mod(&self, modulus: &Polynomial<F>) -> Polynomial<F> {
+        let zero = self.coef[0].zero();
+        let x = Polynomial {
+            coef: vec![zero.clone(), self.coef[0].one()],
+        };
+        let xp = x.pow_mod_big(&self.coef[0].characteristic_big(), modulus);
+        // Horner's rule in x^p
+        self.coef
+            .iter()
+            .rev()
+            .fold(Polynomial { coef: vec![zero] }, |acc, c| {
+                (acc * xp.clone()
+                    + Polynomial {
+                        coef: vec![c.frobenius()],
+                    })
+                    % modulus.clone()
+            })
+    }
+
+    fn adjust_func(&mut self) -> Polynomial<F> {
         /* Adjust the function to fit the format
 
         examples:
         if coef == [] -> coef = [0]
         if coef == [1,0] -> coef = [1]  i.e. 1 + 0*x -> 1
          */
-        let mut coef_inv: Vec<FiniteField> = self.coef.clone();
-        coef_inv.reverse();
-        for _ in 0..coef_inv.len() {
-            if coef_inv[0].is_0() {
-                coef_inv.remove(0);
-            } else {
-                break;
-            }
+        let mut coef: Vec<F> = self.coef.clone();
+        while coef.last().is_some_and(|c| c.is_zero()) {
+            coef.pop();
         }
-        coef_inv.reverse();
-        if coef_inv.len() == 0 {
-            coef_inv.push(FiniteField {
-                char: self.coef[0].char,
-                element: self.coef[0].get_0().element,
-            });
+        if coef.is_empty() {
+            coef.push(self.coef[0].zero());
         };
 
-        Polynomial { coef: coef_inv }
+        Polynomial { coef }
     }
 
     /// get GCD of two polynomials
     /// examples:
     /// f.gcd(g) means GCD(f,g)
-    pub fn gcd(&self, other: Polynomial) -> Polynomial {
-        let mut f: Polynomial = self.clone();
-        let mut g: Polynomial = other.clone();
-        let answer: Polynomial;
+    pub fn gcd(&self, other: Polynomial<F>) -> Polynomial<F> {
+        let mut f: Polynomial<F> = self.clone();
+        let mut g: Polynomial<F> = other;
+        let answer: Polynomial<F>;
 
         if f.coef.len() < g.coef.len() {
             (f, g) = (g, f);
@@ -381,17 +533,8 @@ impl Polynomial {
             f = f.adjust_func();
             g = g.adjust_func();
 
-            let mut flag_0 = true;
-            // every coef of g is 0 -> flag_0 = true
-            for i in 0..g.coef.len() {
-                if !g.coef[i].is_0() {
-                    flag_0 = false;
-                    break;
-                }
-            }
-
-            // end
-            if flag_0 {
+            // end when every coef of g is 0
+            if g.coef.iter().all(|c| c.is_zero()) {
                 answer = f;
                 break;
             }
@@ -403,6 +546,7 @@ impl Polynomial {
         answer
     }
 }
+
 impl FiniteField {
     /// Obtain the 0 of the finite field.
     /// There are two types of finite fields: prime field and galois field.
@@ -414,16 +558,7 @@ impl FiniteField {
                 char: self.char,
                 element: Element::PrimeField { element: 0 },
             },
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: pp,
-            } => FiniteField {
-                char: self.char,
-                element: Element::GaloisField {
-                    element: vec![0],
-                    primitive_polynomial: pp.clone(),
-                },
-            },
+            Element::GaloisField { element: _, field } => field.zero(),
         }
     }
     /// Obtain the 1 of the finite field.
@@ -436,82 +571,197 @@ impl FiniteField {
                 char: self.char,
                 element: Element::PrimeField { element: 1 },
             },
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: pp,
-            } => FiniteField {
+            Element::GaloisField { element: _, field } => field.one(),
+        }
+    }
+    /// self^e by square-and-multiply.
+    ///
+    /// In a GF with log tables this is a table lookup.
+    pub fn pow(&self, e: u128) -> FiniteField {
+        self.pow_big(&[e as u64, (e >> 64) as u64])
+    }
+
+    /// self^e for an exponent given as little-endian 64-bit limbs, e.g. [0, 0, 1] = 2^128.
+    pub fn pow_big(&self, e: &[u64]) -> FiniteField {
+        match &self.element {
+            Element::PrimeField { element: _ } => {
+                let mut result = self.get_1();
+                for bit in exponent_bits(e) {
+                    result = result.clone() * result;
+                    if bit {
+                        result = result * self.clone();
+                    }
+                }
+                result
+            }
+            Element::GaloisField { element: f, field } => FiniteField {
                 char: self.char,
                 element: Element::GaloisField {
-                    element: vec![1],
-                    primitive_polynomial: pp.clone(),
+                    element: field.pow_coef(f, e),
+                    field: field.clone(),
                 },
             },
         }
     }
+
+    /// Frobenius map x -> x^p.
+    ///
+    /// In GF(p^n) this is a linear map over F_p, so it is computed from the precomputed images x^(ip)
+    /// of the basis instead of by exponentiation.
+    pub fn frobenius(&self) -> FiniteField {
+        match &self.element {
+            Element::PrimeField { element: _ } => self.clone(),
+            Element::GaloisField { element: f, field } => FiniteField {
+                char: self.char,
+                element: Element::GaloisField {
+                    element: field.frobenius_coef(f),
+                    field: field.clone(),
+                },
+            },
+        }
+    }
+
     /// Determine if the FiniteField is 0.
     pub fn is_0(&self) -> bool {
         // check if the element is 0
+        let p = coefficient_modulus(self.char);
         match &self.element {
-            Element::PrimeField { element: e } => *e == 0,
+            Element::PrimeField { element: e } => *e % p == 0,
             Element::GaloisField {
                 element: e,
-                primitive_polynomial: _,
-            } => {
-				if e.len() == 0{
-					return true;
-				}else{
-					
-					(e[0] == 0) && (e.len() == 1)}
-			}
-		}
+                field: _,
+            } => e.iter().all(|&c| c % p == 0),
+        }
     }
 
     /// Determine if the FiniteField is 1.
     pub fn is_1(&self) -> bool {
         // check if the element is 1
+        let p = coefficient_modulus(self.char);
         match &self.element {
-            Element::PrimeField { element: e } => *e == 1,
+            Element::PrimeField { element: e } => (*e - 1) % p == 0,
             Element::GaloisField {
                 element: e,
-                primitive_polynomial: _,
-            } => {
-                let sum: NumType = e.iter().sum();
-                (e[0] == 1) && (sum == 1)
+                field: _,
+            } => !e.is_empty() && (e[0] - 1) % p == 0 && e[1..].iter().all(|&c| c % p == 0),
+        }
+    }
+}
+
+impl Field for FiniteField {
+    fn zero(&self) -> FiniteField {
+        self.get_0()
+    }
+    fn one(&self) -> FiniteField {
+        self.get_1()
+    }
+    fn inv(&self) -> FiniteField {
+        assert!(!self.is_0(), "0 has no inverse");
+        self.get_1() / self.clone()
+    }
+    fn characteristic(&self) -> u64 {
+        self.char
+    }
+    fn order(&self) -> u128 {
+        match &self.element {
+            Element::PrimeField { element: _ } => self.char as u128,
+            Element::GaloisField { element: _, field } => field.order(),
+        }
+    }
+    fn is_zero(&self) -> bool {
+        self.is_0()
+    }
+    fn is_one(&self) -> bool {
+        self.is_1()
+    }
+    fn pow(&self, e: u128) -> FiniteField {
+        FiniteField::pow(self, e)
+    }
+    fn pow_big(&self, e: &[u64]) -> FiniteField {
+        FiniteField::pow_big(self, e)
+    }
+    fn frobenius(&self) -> FiniteField {
+        FiniteField::frobenius(self)
+    }
+}
+
+impl PartialEq for FiniteField {
+    fn eq(&self, other: &FiniteField) -> bool {
+        if self.char != other.char {
+            return false;
+        }
+        let p = coefficient_modulus(self.char);
+        match (&self.element, &other.element) {
+            (Element::PrimeField { element: x }, Element::PrimeField { element: y }) => {
+                (x - y) % p == 0
+            }
+            (
+                Element::GaloisField {
+                    element: x,
+                    field: field_x,
+                },
+                Element::GaloisField {
+                    element: y,
+                    field: field_y,
+                },
+            ) => {
+                let len = x.len().max(y.len());
+                let coef = |v: &Vec<NumType>, i: usize| v.get(i).copied().unwrap_or(0);
+                field_x == field_y && (0..len).all(|i| (coef(x, i) - coef(y, i)) % p == 0)
             }
+            _ => false,
         }
     }
 }
+
+impl Eq for FiniteField {}
+
+impl<F: Field> PartialEq for Polynomial<F> {
+    fn eq(&self, other: &Polynomial<F>) -> bool {
+        let len = self.coef.len().max(other.coef.len());
+        (0..len).all(|i| match (self.coef.get(i), other.coef.get(i)) {
+            (Some(a), Some(b)) => a == b,
+            (Some(c), None) | (None, Some(c)) => c.is_zero(),
+            (None, None) => true,
+        })
+    }
+}
+
+impl<F: Field> PartialEq for Matrix<F> {
+    fn eq(&self, other: &Matrix<F>) -> bool {
+        self.element == other.element
+    }
+}
+
 // Matrix
-impl ops::Add for Matrix {
-    type Output = Matrix;
-    fn add(self, other: Matrix) -> Matrix {
+impl<F: Field> ops::Add for Matrix<F> {
+    type Output = Matrix<F>;
+    fn add(self, other: Matrix<F>) -> Matrix<F> {
         // add two matrices
-        let mut result: Matrix = Matrix { element: vec![] };
-        for i in 0..self.element.len() {
-            let mut row: Vec<FiniteField> = vec![];
-            for j in 0..self.element[0].len() {
-                row.push(self.element[i][j].clone() + other.element[i][j].clone());
-            }
+        let mut result: Matrix<F> = Matrix { element: vec![] };
+        for (row_a, row_b) in self.element.iter().zip(other.element.iter()) {
+            let row: Vec<F> = row_a
+                .iter()
+                .zip(row_b.iter())
+                .map(|(a, b)| a.clone() + b.clone())
+                .collect();
             result.element.push(row);
         }
         result
     }
 }
 
-impl ops::Mul for Matrix {
-    type Output = Matrix;
-    fn mul(self, other: Matrix) -> Matrix {
+impl<F: Field> ops::Mul for Matrix<F> {
+    type Output = Matrix<F>;
+    fn mul(self, other: Matrix<F>) -> Matrix<F> {
         // multiply two matrices
-        let mut result: Matrix = Matrix { element: vec![] };
-        for i in 0..self.element.len() {
-            let mut row: Vec<FiniteField> = vec![];
+        let mut result: Matrix<F> = Matrix { element: vec![] };
+        for row_a in &self.element {
+            let mut row: Vec<F> = vec![];
             for j in 0..other.element[0].len() {
-                let mut sum: FiniteField = FiniteField {
-                    char: self.element[i][j].char,
-                    element: Element::PrimeField { element: 0 },
-                };
-                for k in 0..self.element[0].len() {
-                    sum = sum + self.element[i][k].clone() * other.element[k][j].clone();
+                let mut sum: F = row_a[0].zero();
+                for (k, a) in row_a.iter().enumerate() {
+                    sum = sum + a.clone() * other.element[k][j].clone();
                 }
                 row.push(sum);
             }
@@ -521,20 +771,12 @@ impl ops::Mul for Matrix {
     }
 }
 // Polynomial
-impl ops::Add for Polynomial {
-    type Output = Polynomial;
-    fn add(self, other: Polynomial) -> Polynomial {
+impl<F: Field> ops::Add for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn add(self, other: Polynomial<F>) -> Polynomial<F> {
         let mut result = Polynomial { coef: Vec::new() };
-        let max_degree = if self.coef.len() > other.coef.len() {
-            self.coef.len()
-        } else {
-            other.coef.len()
-        };
-        let min_degree = if self.coef.len() < other.coef.len() {
-            self.coef.len()
-        } else {
-            other.coef.len()
-        };
+        let max_degree = self.coef.len().max(other.coef.len());
+        let min_degree = self.coef.len().min(other.coef.len());
 
         for i in 0..min_degree {
             result
@@ -552,20 +794,13 @@ impl ops::Add for Polynomial {
     }
 }
 
-impl ops::Sub for Polynomial {
-    type Output = Polynomial;
-    fn sub(self, other: Polynomial) -> Polynomial {
+impl<F: Field> ops::Sub for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn sub(self, other: Polynomial<F>) -> Polynomial<F> {
         let mut result = Polynomial { coef: Vec::new() };
-        let max_degree = if self.coef.len() > other.coef.len() {
-            self.coef.len()
-        } else {
-            other.coef.len()
-        };
-        let min_degree = if self.coef.len() < other.coef.len() {
-            self.coef.len()
-        } else {
-            other.coef.len()
-        };
+        let max_degree = self.coef.len().max(other.coef.len());
+        let min_degree = self.coef.len().min(other.coef.len());
+
         for i in 0..min_degree {
             result
                 .coef
@@ -581,115 +816,42 @@ impl ops::Sub for Polynomial {
         result.adjust_func()
     }
 }
-impl ops::Mul for Polynomial {
-    type Output = Polynomial;
-    fn mul(self, other: Polynomial) -> Polynomial {
-        let element0 = self.coef[0].clone().get_0();
+impl<F: Field> ops::Mul for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn mul(self, other: Polynomial<F>) -> Polynomial<F> {
+        let element0 = self.coef[0].zero();
         let mut result = Polynomial {
             coef: vec![element0; self.coef.len() + other.coef.len() - 1],
         };
-        for i in 0..self.coef.len() {
-            for j in 0..other.coef.len() {
-                let tmp = self.coef[i].clone() * other.coef[j].clone();
+        for (i, a) in self.coef.iter().enumerate() {
+            for (j, b) in other.coef.iter().enumerate() {
+                let tmp = a.clone() * b.clone();
                 result.coef[i + j] = result.coef[i + j].clone() + tmp;
             }
         }
         result.adjust_func()
     }
 }
-impl ops::Div for Polynomial {
-    type Output = Polynomial;
-    fn div(self, other: Polynomial) -> Polynomial {
-        let mut quotient = Polynomial { coef: Vec::new() };
-		let f = self.clone().adjust_func();
-		let g = other.clone().adjust_func();
-		
-		let mut f_inv = f.coef;
-		f_inv.reverse();
-		let mut g_inv = g.coef;
-		g_inv.reverse();
-
-		// drop0
-		for _ in 0..f_inv.len() {
-			if f_inv.len() <= 1 {
-				break;
-			}
-			else if f_inv[0].is_0() {
-				f_inv.remove(0);
-			} else {
-				break;
-			}
-		}
-		for _ in 0..g_inv.len() {
-			if g_inv.len() <= 1 {
-				break;
-			}
-			else if g_inv[0].is_0() {
-				g_inv.remove(0);
-			} else {
-				break;
-			}
-		}
-
-		if f_inv.len() < g_inv.len() {
-			quotient = Polynomial {
-				coef: vec![self.coef[0].clone().get_0()],
-			};
-		} else {
-			for i in 0..f_inv.len() - g_inv.len() + 1 {
-				let temp = f_inv[i].clone() / g_inv[0].clone();
-				for j in 0..g_inv.len() {
-					f_inv[i + j] = f_inv[i + j].clone() - (temp.clone() * g_inv[j].clone());
-				}
-				quotient.coef.push(temp);
-			}
-		}
-
-		// reverse
-		quotient = Polynomial {
-			coef: quotient.coef.clone().into_iter().rev().collect(),
-		};
-		quotient.adjust_func()
-    }
-}
-impl ops::Rem for Polynomial {
-    type Output = Polynomial;
-    fn rem(self, other: Polynomial) -> Polynomial {
+impl<F: Field> ops::Div for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn div(self, other: Polynomial<F>) -> Polynomial<F> {
         let mut quotient = Polynomial { coef: Vec::new() };
-        let mut f_inv = self.coef.clone();
+        let f = self.clone().adjust_func();
+        let g = other.clone().adjust_func();
+
+        let mut f_inv = f.coef;
         f_inv.reverse();
-        let mut g_inv = other.coef.clone();
+        let mut g_inv = g.coef;
         g_inv.reverse();
 
-        // drop0
-        for _ in 0..f_inv.len() {
-            if f_inv.len() <= 1 {
-                break;
-            }
-            if f_inv[0].is_0() {
-                f_inv.remove(0);
-            } else {
-                break;
-            }
-        }
-        for _ in 0..g_inv.len() {
-            if g_inv.len() <= 1 {
-                break;
-            }
-            if g_inv[0].is_0() {
-                g_inv.remove(0);
-            } else {
-                break;
-            }
-        }
-
         if f_inv.len() < g_inv.len() {
             quotient = Polynomial {
-                coef: vec![self.coef[0].clone().get_0()],
+                coef: vec![self.coef[0].zero()],
             };
         } else {
+            let head_inv = g_inv[0].inv();
             for i in 0..f_inv.len() - g_inv.len() + 1 {
-                let temp = f_inv[i].clone() / g_inv[0].clone();
+                let temp = f_inv[i].clone() * head_inv.clone();
                 for j in 0..g_inv.len() {
                     f_inv[i + j] = f_inv[i + j].clone() - (temp.clone() * g_inv[j].clone());
                 }
@@ -697,24 +859,38 @@ impl ops::Rem for Polynomial {
             }
         }
 
-        // drop0
-        for _ in 0..f_inv.len() {
-            if f_inv[0].is_0() {
-                f_inv.remove(0);
-            } else {
-                break;
+        // reverse
+        quotient.coef.reverse();
+        quotient.adjust_func()
+    }
+}
+impl<F: Field> ops::Rem for Polynomial<F> {
+    type Output = Polynomial<F>;
+    fn rem(self, other: Polynomial<F>) -> Polynomial<F> {
+        let f = self.clone().adjust_func();
+        let g = other.clone().adjust_func();
+
+        let mut f_inv = f.coef;
+        f_inv.reverse();
+        let mut g_inv = g.coef;
+        g_inv.reverse();
+
+        if f_inv.len() >= g_inv.len() {
+            let head_inv = g_inv[0].inv();
+            for i in 0..f_inv.len() - g_inv.len() + 1 {
+                let temp = f_inv[i].clone() * head_inv.clone();
+                for j in 0..g_inv.len() {
+                    f_inv[i + j] = f_inv[i + j].clone() - (temp.clone() * g_inv[j].clone());
+                }
             }
         }
-        let mut remainder: Polynomial;
 
-        if f_inv.len() == 0 {
-            remainder = Polynomial {
-                coef: vec![self.coef[0].clone().get_0()],
-            };
-        } else {
-            remainder = Polynomial {
-                coef: f_inv.clone().into_iter().rev().collect(),
-            };
+        // drop0
+        let mut remainder = Polynomial {
+            coef: f_inv.into_iter().rev().collect(),
+        };
+        if remainder.coef.is_empty() {
+            remainder.coef.push(self.coef[0].zero());
         }
         remainder.adjust_func()
     }
@@ -724,108 +900,33 @@ impl ops::Rem for Polynomial {
 impl ops::Add for FiniteField {
     type Output = FiniteField;
     fn add(self, other: FiniteField) -> FiniteField {
-        match self.element {
-            Element::PrimeField { element: _ } => {
-                let mut x: NumType = 0;
-                let mut y: NumType = 0;
-
-                // get element from enum
-                if let Element::PrimeField { element: a } = self.element {
-                    x = a;
-                }
-                if let Element::PrimeField { element: a } = other.element {
-                    y = a;
-                }
-                let mut tmp = (x + y) % self.char as NumType;
-
-                if tmp < 0 {
-                    tmp += self.char as NumType;
-                } else if tmp == self.char as NumType {
-                    tmp = 0;
-                }
+        match (self.element, other.element) {
+            (Element::PrimeField { element: x }, Element::PrimeField { element: y }) => {
+                let p = coefficient_modulus(self.char) as i128;
+                let tmp = (x as i128 + y as i128).rem_euclid(p) as NumType;
 
                 FiniteField {
                     char: self.char,
                     element: Element::PrimeField { element: tmp },
                 }
             }
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: _,
-            } => {
-                let mut result: Vec<NumType> = Vec::new();
-                let mut f: Vec<NumType> = Vec::new();
-                let mut g: Vec<NumType> = Vec::new();
-                let mut h: Polynomial = Polynomial { coef: Vec::new() };
-
-                // get element from enum
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: primitive_func,
-                } = &self.element
-                {
-                    f = func_vec.clone();
-                    h = primitive_func.clone();
-                }
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: primitive_func,
-                } = &other.element
-                {
-                    g = func_vec.clone();
-                    h = primitive_func.clone();
-                }
-
-                // deg f <= deg g
-                if f.len() > g.len() {
-                    (f, g) = (g, f);
-                }
-
-                // calculate
-                for i in 0..f.len() {
-                    let finite_f = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: f[i] },
-                    };
-                    let finite_g = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: g[i] },
-                    };
-                    let temp = finite_f + finite_g;
-                    let mut answer = 0;
-                    if let Element::PrimeField { element: a } = temp.element {
-                        answer = a;
-                    }
-                    result.push(answer);
-                }
-
-                for i in f.len()..g.len() {
-                    let finite_f = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: 0 },
-                    };
-                    let finite_g = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: g[i] },
-                    };
-                    let temp = finite_f + finite_g;
-                    let mut answer = 0;
-                    if let Element::PrimeField { element: a } = temp.element {
-                        answer = a;
-                    }
-                    result.push(answer);
-                }
-
-                // drop0
-                let result = drop0(result);
+            (
+                Element::GaloisField { element: f, field },
+                Element::GaloisField {
+                    element: g,
+                    field: other_field,
+                },
+            ) => {
+                field.assert_same(&other_field);
                 FiniteField {
                     char: self.char,
                     element: Element::GaloisField {
-                        element: result,
-                        primitive_polynomial: h,
+                        element: field.add_coef(&f, &g),
+                        field,
                     },
                 }
             }
+            _ => panic!("cannot add elements of F_p and GF(p^n)"),
         }
     }
 }
@@ -833,123 +934,33 @@ impl ops::Add for FiniteField {
 impl ops::Sub for FiniteField {
     type Output = FiniteField;
     fn sub(self, other: FiniteField) -> FiniteField {
-        match self.element {
-            Element::PrimeField { element: _ } => {
-                let mut x: NumType = 0;
-                let mut y: NumType = 0;
-
-                // get element from enum
-                if let Element::PrimeField { element: a } = self.element {
-                    x = a;
-                }
-                if let Element::PrimeField { element: a } = other.element {
-                    y = a;
-                }
-                let mut tmp = (x - y) % self.char as NumType;
-                if tmp < 0 {
-                    tmp += self.char as NumType;
-                } else if tmp == self.char as NumType {
-                    tmp = 0;
-                }
+        match (self.element, other.element) {
+            (Element::PrimeField { element: x }, Element::PrimeField { element: y }) => {
+                let p = coefficient_modulus(self.char) as i128;
+                let tmp = (x as i128 - y as i128).rem_euclid(p) as NumType;
 
                 FiniteField {
                     char: self.char,
                     element: Element::PrimeField { element: tmp },
                 }
             }
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: _,
-            } => {
-                let mut result: Vec<NumType> = Vec::new();
-                let mut f: Vec<NumType> = Vec::new();
-                let mut g: Vec<NumType> = Vec::new();
-                let mut h: Polynomial = Polynomial { coef: Vec::new() };
-
-                // get element from enum
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: primitive_func,
-                } = &self.element
-                {
-                    f = func_vec.clone();
-                    h = primitive_func.clone();
-                }
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: primitive_func,
-                } = &other.element
-                {
-                    g = func_vec.clone();
-                    h = primitive_func.clone();
-                }
-
-                // get each degree
-                let max_degree = if f.len() > g.len() { f.len() } else { g.len() };
-                let min_degree = if f.len() < g.len() { f.len() } else { g.len() };
-
-                // calculate
-                for i in 0..min_degree {
-                    let finite_f = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: f[i] },
-                    };
-                    let finite_g = FiniteField {
-                        char: self.char,
-                        element: Element::PrimeField { element: g[i] },
-                    };
-                    let temp = finite_f - finite_g;
-                    let mut answer = 0;
-                    if let Element::PrimeField { element: a } = temp.element {
-                        answer = a;
-                    }
-                    result.push(answer);
-                }
-
-                for i in min_degree..max_degree {
-                    let mut answer = 0;
-                    if f.len() > g.len() {
-                        let finite_f = FiniteField {
-                            char: self.char,
-                            element: Element::PrimeField { element: f[i] },
-                        };
-                        let finite_g = FiniteField {
-                            char: self.char,
-                            element: Element::PrimeField { element: 0 },
-                        };
-                        let temp = finite_f - finite_g;
-                        if let Element::PrimeField { element: a } = temp.element {
-                            answer = a;
-                        }
-                    } else {
-                        let finite_f = FiniteField {
-                            char: self.char,
-                            element: Element::PrimeField { element: 0 },
-                        };
-                        let finite_g = FiniteField {
-                            char: self.char,
-                            element: Element::PrimeField { element: g[i] },
-                        };
-                        let temp = finite_f - finite_g;
-
-                        if let Element::PrimeField { element: a } = temp.element {
-                            answer = a;
-                        }
-                    }
-
-                    result.push(answer);
-                }
-
-                // drop0
-                let result = drop0(result);
+            (
+                Element::GaloisField { element: f, field },
+                Element::GaloisField {
+                    element: g,
+                    field: other_field,
+                },
+            ) => {
+                field.assert_same(&other_field);
                 FiniteField {
                     char: self.char,
                     element: Element::GaloisField {
-                        element: result,
-                        primitive_polynomial: h,
+                        element: field.sub_coef(&f, &g),
+                        field,
                     },
                 }
             }
+            _ => panic!("cannot subtract elements of F_p and GF(p^n)"),
         }
     }
 }
@@ -957,123 +968,33 @@ impl ops::Sub for FiniteField {
 impl ops::Mul for FiniteField {
     type Output = FiniteField;
     fn mul(self, other: FiniteField) -> FiniteField {
-        match self.element {
-            Element::PrimeField { element: _ } => {
-                let mut x = 0;
-                let mut y = 0;
-                if let Element::PrimeField { element: a } = self.element {
-                    x = a;
-                }
-                if let Element::PrimeField { element: a } = other.element {
-                    y = a;
-                }
-                let tmp = (x * y) % self.char as NumType;
+        match (self.element, other.element) {
+            (Element::PrimeField { element: x }, Element::PrimeField { element: y }) => {
+                // i128 keeps the product exact for characteristics up to 2^63
+                let p = coefficient_modulus(self.char) as i128;
+                let tmp = (x as i128 * y as i128).rem_euclid(p) as NumType;
                 FiniteField {
                     char: self.char,
                     element: Element::PrimeField { element: tmp },
                 }
             }
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: _,
-            } => {
-				let mut f: Vec<NumType> = Vec::new();
-				let mut g: Vec<NumType> = Vec::new();
-				let mut primitive_polynomial: Polynomial = Polynomial { coef: Vec::new() };
-
-			
-				// get element from enum
-				if let Element::GaloisField {
-					element: func_vec,
-					primitive_polynomial: pp,
-				} = &self.element
-				{
-					f = func_vec.clone();
-					primitive_polynomial = pp.clone();
-				}
-				if let Element::GaloisField {
-					element: func_vec,
-					primitive_polynomial: pp,
-				} = &other.element
-				{
-					g = func_vec.clone();
-					primitive_polynomial = pp.clone();
-				}
-				let element0: Element = Element::GaloisField { element: vec![0], primitive_polynomial: primitive_polynomial.clone() };
-				let prime0: FiniteField = FiniteField {
-					char: self.char,
-					element: element0,
-				};
-				if f.len() == 0{
-					f.push(0);
-				}
-				if g.len() == 0{
-					g.push(0);
-				}
-				
-				let mut result = vec![prime0; f.len() + g.len() - 1];
-				for i in 0..f.len() {
-					for j in 0..g.len() {
-						let r_tmp = FiniteField {
-							char: self.char,
-							element: result[i + j].element.clone(),
-						};
-						let f_tmp = FiniteField {
-							char: self.char,
-							element: Element::PrimeField { element: f[i] },
-						};
-						let g_tmp = FiniteField {
-							char: self.char,
-							element: Element::PrimeField { element: g[j] },
-						};
-
-						result[i + j] = r_tmp + f_tmp * g_tmp;
-					}
-				}
-
-				let mut result_inv = result.clone();
-				result_inv.reverse();
-				let mut primitive_polynomial_inv = primitive_polynomial.clone();
-				primitive_polynomial_inv.coef.reverse();
-
-				if result_inv.len() >= primitive_polynomial_inv.coef.len() {
-					for i in 0..result_inv.len() - primitive_polynomial_inv.coef.len() + 1 {
-						let temp = result_inv[i].clone() / primitive_polynomial_inv.coef[0].clone();
-						for j in 0..primitive_polynomial_inv.coef.len() {
-							result_inv[i + j] = result_inv[i + j].clone()
-								- (temp.clone() * primitive_polynomial_inv.coef[j].clone());
-						}
-					}
-				}
-				// drop0
-				for _ in 0..result_inv.len() {
-					if let Element::PrimeField { element: a } = result_inv[0].element {
-						if a != 0 {
-							break;
-						} else {
-							result_inv.remove(0);
-						}
-					}
-				}
-				let mut result = result_inv.clone();
-				result.reverse();
-
-				// PrimeField -> NumType
-				let mut result_num: Vec<NumType> = Vec::new();
-				for i in 0..result.len() {
-					if let Element::PrimeField { element: a } = result[i].element {
-						result_num.push(a);
-					}
-				}
-
-				FiniteField {
-					char: self.char,
-					element: Element::GaloisField {
-						element: result_num,
-						primitive_polynomial: primitive_polynomial,
-					},
-				}
+            (
+                Element::GaloisField { element: f, field },
+                Element::GaloisField {
+                    element: g,
+                    field: other_field,
+                },
+            ) => {
+                field.assert_same(&other_field);
+                FiniteField {
+                    char: self.char,
+                    element: Element::GaloisField {
+                        element: field.mul_coef(&f, &g),
+                        field,
+                    },
+                }
             }
+            _ => panic!("cannot multiply elements of F_p and GF(p^n)"),
         }
     }
 }
@@ -1082,93 +1003,36 @@ impl ops::Div for FiniteField {
     type Output = FiniteField;
     // ユークリッドの互除法
     fn div(self, other: FiniteField) -> FiniteField {
-        match self.element {
-            Element::PrimeField { element: _ } => {
-                let mut x: NumType = 0;
-                let mut y: NumType = 0;
-                if let Element::PrimeField { element: a } = self.element {
-                    x = a;
-                }
-                if let Element::PrimeField { element: a } = other.element {
-                    y = a;
-                }
-                let mut t = extended_euclidean(self.char as NumType, y);
-
-                if t < 0 {
-                    let mut i = 1;
-
-                    while (t + i * self.char as NumType) < 0 {
-                        i += 1;
-                    }
-                    t = (t + i * self.char as NumType) % self.char as NumType;
-                }
+        match (self.element, other.element) {
+            (Element::PrimeField { element: x }, Element::PrimeField { element: y }) => {
+                let p = coefficient_modulus(self.char);
+                assert!(y.rem_euclid(p) != 0, "division by zero");
+                let t = extended_euclidean(p, y.rem_euclid(p));
                 FiniteField {
                     char: self.char,
                     element: Element::PrimeField {
-                        element: (x * t) % self.char as NumType,
+                        element: (x.rem_euclid(p) as i128 * t as i128 % p as i128) as NumType,
                     },
                 }
             }
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: _,
-            } => {
-                let mut f: FiniteField = FiniteField {
-                    char: self.char,
-                    element: Element::PrimeField { element: 0 },
-                };
+            (
+                Element::GaloisField { element: f, field },
+                Element::GaloisField {
+                    element: g,
+                    field: other_field,
+                },
+            ) => {
+                field.assert_same(&other_field);
 
-                let mut g: FiniteField = FiniteField {
+                FiniteField {
                     char: self.char,
-                    element: Element::PrimeField { element: 0 },
-                };
-
-                let mut primitive_polynomial: Polynomial = Polynomial { coef: Vec::new() };
-
-                // get element from enum
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: pp,
-                } = &self.element
-                {
-                    f = FiniteField {
-                        char: self.char,
-                        element: Element::GaloisField {
-                            element: func_vec.clone(),
-                            primitive_polynomial: pp.clone(),
-                        },
-                    };
-                    primitive_polynomial = pp.clone();
-                }
-                if let Element::GaloisField {
-                    element: func_vec,
-                    primitive_polynomial: pp,
-                } = &other.element
-                {
-                    g = FiniteField {
-                        char: self.char,
-                        element: Element::GaloisField {
-                            element: func_vec.clone(),
-                            primitive_polynomial: pp.clone(),
-                        },
-                    };
-                }
-
-                // g^(-1) = g^(q-2) mod primitive_polynomial
-                let pow_count = self
-                    .char
-                    .pow((primitive_polynomial.coef.len() - 1).try_into().unwrap())
-                    - 2;
-                let g_origin = g.clone();
-                for _ in 0..pow_count - 1 {
-                    g = g * g_origin.clone();
+                    element: Element::GaloisField {
+                        element: field.div_coef(&f, &g),
+                        field,
+                    },
                 }
-
-                // f * g^(-1) mod primitive_polynomial
-                let result: FiniteField = f * g;
-
-                result
             }
+            _ => panic!("cannot divide elements of F_p and GF(p^n)"),
         }
     }
 }
@@ -1182,61 +1046,55 @@ impl ops::Neg for FiniteField {
                     char: self.char,
                     element: Element::PrimeField { element: 0 },
                 };
-                let result = prime0 - self;
-                result
-            }
-            Element::GaloisField {
-                element: _,
-                primitive_polynomial: _,
-            } => {
-                let mut primitive_polynomial: Polynomial = Polynomial { coef: Vec::new() };
-                if let Element::GaloisField {
-                    element: _,
-                    primitive_polynomial: pp,
-                } = &self.element
-                {
-                    primitive_polynomial = pp.clone();
-                }
-                let galois0 = FiniteField {
-                    char: self.char,
-                    element: Element::GaloisField {
-                        element: vec![0],
-                        primitive_polynomial: primitive_polynomial.clone(),
-                    },
-                };
-                let result = galois0 - self;
-                result
+                prime0 - self
             }
+            Element::GaloisField { element: f, field } => FiniteField {
+                char: self.char,
+                element: Element::GaloisField {
+                    element: field.neg_coef(&f),
+                    field,
+                },
+            },
         }
     }
 }
 
+// the characteristic as the modulus of NumType coefficients, which requires p < 2^63
+pub(crate) fn coefficient_modulus(char: u64) -> NumType {
+    assert!(
+        char <= NumType::MAX as u64,
+        "characteristic {} is not below 2^63, the limit of i64 coefficients",
+        char
+    );
+    char as NumType
+}
+
 fn drop0(vec: Vec<NumType>) -> Vec<NumType> {
     let mut vec_inverse = vec.into_iter().rev().collect::<Vec<NumType>>();
-	if vec_inverse.len() == 0 {
-		vec_inverse.push(0);
-		return vec_inverse;
-	}
-	
-	for _ in 0..vec_inverse.len() - 1 {
-		if vec_inverse[0] != 0 {
-			break;
-		} else {
-			vec_inverse.remove(0);
-		}
-	}
-    let vec = vec_inverse.into_iter().rev().collect::<Vec<NumType>>();
-    vec
+    if vec_inverse.is_empty() {
+        vec_inverse.push(0);
+        return vec_inverse;
+    }
+
+    for _ in 0..vec_inverse.len() - 1 {
+        if vec_inverse[0] != 0 {
+            break;
+        } else {
+            vec_inverse.remove(0);
+        }
+    }
+    vec_inverse.into_iter().rev().collect::<Vec<NumType>>()
 }
 
 fn extended_euclidean(u: NumType, v: NumType) -> NumType {
-    // Euclidean reciprocal division on real numbers
+    // Euclidean reciprocal division on real numbers, in i128 so that u may be up to 2^63
+    let (u, v) = (u as i128, v as i128);
     let mut r0 = u;
     let mut r1 = v;
-    let mut s0 = 1;
-    let mut s1 = 0;
-    let mut t0 = 0;
-    let mut t1 = 1;
+    let mut s0: i128 = 1;
+    let mut s1: i128 = 0;
+    let mut t0: i128 = 0;
+    let mut t1: i128 = 1;
     while r1 != 0 {
         let q = r0 / r1;
         let r = r0 - q * r1;
@@ -1250,12 +1108,22 @@ fn extended_euclidean(u: NumType, v: NumType) -> NumType {
         t1 = t;
     }
     if t0 < 0 {
-        t0 + u
+        (t0 + u) as NumType
     } else {
-        t0
+        t0 as NumType
     }
 }
-fn change_base_from10_to_n(x: NumType, n: NumType) -> Vec<NumType> {
+// bits of a little-endian limb exponent, most significant first, without leading zeros
+fn exponent_bits(e: &[u64]) -> impl Iterator<Item = bool> + '_ {
+    let bits = e.len() * 64;
+    let top = (0..bits)
+        .rev()
+        .find(|&i| e[i / 64] >> (i % 64) & 1 == 1)
+        .map_or(0, |i| i + 1);
+    (0..top).rev().map(move |i| e[i / 64] >> (i % 64) & 1 == 1)
+}
+
+fn change_base_from10_to_n(x: u128, n: u128) -> Vec<NumType> {
     // Generate coefficient lists in sequence.
     // examples: when x = 1, n = 2, return [1] i.e. 1
     //                  when x = 2, n = 2, return [0, 1]  i.e. x
@@ -1263,28 +1131,323 @@ fn change_base_from10_to_n(x: NumType, n: NumType) -> Vec<NumType> {
     let mut result = Vec::new();
     let mut x = x;
     while x > 0 {
-        result.push(x % n);
-        x = x / n;
+        result.push((x % n) as NumType);
+        x /= n;
     }
     result
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::*;
 
-    #[cfg(test)]
-    use crate::Element;
-    use crate::FiniteField;
+    #[test]
     fn it_works() {
-        let char: u32 = 2;
+        let char: u64 = 2;
         let element0 = FiniteField {
-            char: char,
+            char,
             element: Element::PrimeField { element: 0 },
         };
         let element1 = FiniteField {
-            char: char,
+            char,
             element: Element::PrimeField { element: 1 },
         };
         let answer = element0 + element1;
+        assert!(answer.is_1());
+    }
+
+    #[test]
+    fn galois_field_mul_div() {
+        // GF(2^4) with x^4 + x + 1
+        let gf = GF::new(2, 4);
+        let elem = |i: NumType| gf.elem(&change_base_from10_to_n(i as u128, 2));
+        assert_eq!(elem(2) * elem(12), elem(11));
+        for a in 1..16 {
+            for b in 1..16 {
+                assert_eq!((elem(a) * elem(b)) / elem(b), elem(a));
+            }
+        }
+    }
+
+    #[test]
+    fn zero_and_one_up_to_multiples_of_p() {
+        // 5 and -4 are 0 and 1 in F_5
+        let prime = |e: NumType| FiniteField {
+            char: 5,
+            element: Element::PrimeField { element: e },
+        };
+        assert!(prime(5).is_0());
+        assert!(prime(-4).is_1());
+        // [0, 0] is 0 and [1, 2] is 1 in GF(2^2)
+        let gf = GF::new(2, 2);
+        let galois = |e: Vec<NumType>| FiniteField {
+            char: 2,
+            element: Element::GaloisField {
+                element: e,
+                field: gf.clone(),
+            },
+        };
+        assert!(galois(vec![0, 0]).is_0());
+        assert!(galois(vec![1, 2]).is_1());
+        assert!(!galois(vec![1, 1]).is_1());
+    }
+
+    #[test]
+    fn sweep_method_skips_pivotless_columns() {
+        let matrix = |rows: &[&[NumType]]| Matrix {
+            element: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&e| FiniteField {
+                            char: 5,
+                            element: Element::PrimeField { element: e },
+                        })
+                        .collect()
+                })
+                .collect(),
+        };
+        // the first column is 0
+        let m = matrix(&[&[0, 1, 2], &[0, 2, 4], &[0, 0, 1]]);
+        assert_eq!(
+            m.sweep_method(),
+            matrix(&[&[0, 1, 0], &[0, 0, 1], &[0, 0, 0]])
+        );
+        // more rows than columns
+        let m = matrix(&[&[1, 2], &[2, 1], &[1, 1]]);
+        assert_eq!(m.sweep_method(), matrix(&[&[1, 0], &[0, 1], &[0, 0]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        // 7 is 0 in F_7
+        let f7 = |x: NumType| FiniteField {
+            char: 7,
+            element: Element::PrimeField { element: x },
+        };
+        let _ = f7(1) / f7(7);
+    }
+
+    #[test]
+    #[should_panic(expected = "not below 2^63")]
+    fn characteristic_above_i64() {
+        // 2^64 - 59 would wrap to a negative modulus
+        let x = FiniteField {
+            char: 18446744073709551557,
+            element: Element::PrimeField { element: 3 },
+        };
+        let _ = x.clone() * x;
+    }
+
+    // F_7 written from scratch, to check that the generic code only relies on `Field`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct F7(u8);
+
+    impl std::ops::Add for F7 {
+        type Output = F7;
+        fn add(self, other: F7) -> F7 {
+            F7((self.0 + other.0) % 7)
+        }
+    }
+    impl std::ops::Sub for F7 {
+        type Output = F7;
+        fn sub(self, other: F7) -> F7 {
+            F7((self.0 + 7 - other.0) % 7)
+        }
+    }
+    impl std::ops::Mul for F7 {
+        type Output = F7;
+        fn mul(self, other: F7) -> F7 {
+            F7((self.0 * other.0) % 7)
+        }
+    }
+    impl std::ops::Neg for F7 {
+        type Output = F7;
+        fn neg(self) -> F7 {
+            F7((7 - self.0) % 7)
+        }
+    }
+    impl Field for F7 {
+        fn zero(&self) -> F7 {
+            F7(0)
+        }
+        fn one(&self) -> F7 {
+            F7(1)
+        }
+        fn inv(&self) -> F7 {
+            F7((1..7).find(|&y| (self.0 * y) % 7 == 1).unwrap())
+        }
+        fn characteristic(&self) -> u64 {
+            7
+        }
+        fn order(&self) -> u128 {
+            7
+        }
+    }
+
+    #[test]
+    fn generic_polynomial_and_matrix() {
+        let poly = |c: &[u8]| Polynomial {
+            coef: c.iter().map(|&x| F7(x)).collect(),
+        };
+        // (x + 1)(x + 2) = x^2 + 3x + 2, (x + 1)(x + 3) = x^2 + 4x + 3
+        let f = poly(&[2, 3, 1]);
+        let g = poly(&[3, 4, 1]);
+        assert_eq!(f.clone() / poly(&[1, 1]), poly(&[2, 1]));
+        assert_eq!(f.clone() % poly(&[1, 1]), poly(&[0]));
+        let h = f.gcd(g);
+        assert_eq!(h.clone() * poly(&[h.coef[1].inv().0]), poly(&[1, 1]));
+        assert_eq!(poly(&[2, 3, 1]).assign_value(F7(5)), F7(0));
+
+        let m = Matrix {
+            element: vec![vec![F7(0), F7(2), F7(1)], vec![F7(3), F7(1), F7(4)]],
+        };
+        let expected = Matrix {
+            element: vec![vec![F7(1), F7(0), F7(0)], vec![F7(0), F7(1), F7(4)]],
+        };
+        assert_eq!(m.sweep_method(), expected);
+    }
+
+    #[test]
+    fn polynomial_pow() {
+        let poly = |c: &[u8]| Polynomial {
+            coef: c.iter().map(|&x| F7(x)).collect(),
+        };
+        let f = poly(&[3, 1, 5]);
+        let m = poly(&[1, 0, 2, 0, 1]);
+        let naive = (0..20).fold(poly(&[1]), |acc, _| acc * f.clone());
+        assert_eq!(f.pow(20), naive);
+        assert_eq!(f.pow_mod(20, &m), naive % m.clone());
+        assert_eq!(f.pow_mod_big(&[0, 1], &m), f.pow_mod(1 << 64, &m));
+        assert_eq!(f.frobenius_mod(&m), f.pow_mod(7, &m));
+        assert_eq!(F7(3).pow(6), F7(1));
+    }
+
+    #[test]
+    fn large_characteristic() {
+        // matches Fp<P>, which computes through u128
+        fn check<const P: u64>() {
+            let ff = |x: u64| FiniteField {
+                char: P,
+                element: Element::PrimeField {
+                    element: x as NumType,
+                },
+            };
+            let to_ff = |x: Fp<P>| ff(x.value());
+            for (a, b) in [(P - 1, P - 2), (P / 3 + 5, P - 1), (1 << 40, (1 << 50) + 1)] {
+                let (x, y) = (Fp::<P>::new(a), Fp::<P>::new(b));
+                assert_eq!(ff(a) + ff(b), to_ff(x + y));
+                assert_eq!(ff(a) - ff(b), to_ff(x - y));
+                assert_eq!(ff(a) * ff(b), to_ff(x * y));
+                assert_eq!(ff(a) / ff(b), to_ff(x / y));
+                assert_eq!(-ff(a), to_ff(-x));
+                assert_eq!(ff(a) * ff(a).inv(), ff(1));
+            }
+            // x^p would need p coefficients before reduction
+            let poly = |c: &[u64]| Polynomial {
+                coef: c.iter().map(|&x| Fp::<P>::new(x)).collect(),
+            };
+            let (f, m) = (poly(&[3, P - 5, 7]), poly(&[2, 0, 0, 1]));
+            assert_eq!(f.frobenius_mod(&m), f.pow_mod(P as u128, &m));
+        }
+        // 2^61 - 1, 29 * 2^57 + 1 and the largest prime below 2^63
+        check::<2305843009213693951>();
+        check::<4179340454199820289>();
+        check::<9223372036854775783>();
+
+        // GF(p^2) = F_p[x] / (x^2 + 1) for p = 2^61 - 1 = 3 mod 4
+        let p: u64 = 2305843009213693951;
+        let c = |x: NumType| FiniteField {
+            char: p,
+            element: Element::PrimeField { element: x },
+        };
+        let gf = GF::with_modulus(Polynomial {
+            coef: vec![c(1), c(0), c(1)],
+        });
+        let a = gf.elem(&[(1 << 60) + 3, (1 << 61) - 5]);
+        assert_eq!(a.clone() * a.inv(), gf.one());
+        assert_eq!(a.pow(gf.order() - 1), gf.one());
+        assert_eq!(gf.gen() * gf.gen(), -gf.one());
+    }
+
+    #[test]
+    fn irreducible_polynomials() {
+        // compare Rabin's test with trial division by every monic polynomial of lower degree
+        let poly = |i: u32, n: usize| {
+            let mut coef: Vec<NumType> = change_base_from10_to_n(i as u128, 3);
+            coef.resize(n, 0);
+            coef.push(1);
+            Polynomial {
+                coef: coef
+                    .iter()
+                    .map(|&x| FiniteField {
+                        char: 3,
+                        element: Element::PrimeField { element: x },
+                    })
+                    .collect(),
+            }
+        };
+        for n in 1..=4 {
+            let mut count = 0;
+            for i in 0..3u32.pow(n as u32) {
+                let f = poly(i, n);
+                let has_factor = (1..=n / 2).any(|d| {
+                    (0..3u32.pow(d as u32)).any(|j| {
+                        let r = f.clone() % poly(j, d);
+                        r.coef.iter().all(|c| c.is_0())
+                    })
+                });
+                assert_eq!(f.is_irreducible(), !has_factor);
+                count += f.is_irreducible() as usize;
+            }
+            assert_eq!(count, [3, 3, 8, 18][n - 1]);
+        }
+
+        // over GF(4), a monic quadratic is irreducible iff it has no root
+        let gf = GF::new(2, 2);
+        let elems = testing::elements(&gf);
+        let mut count = 0;
+        for b in &elems {
+            for c in &elems {
+                let mut f = Polynomial {
+                    coef: vec![c.clone(), b.clone(), gf.one()],
+                };
+                let has_root = elems.iter().any(|a| f.assign_value(a.clone()).is_0());
+                assert_eq!(f.is_irreducible(), !has_root);
+                count += f.is_irreducible() as usize;
+            }
+        }
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn primitive_polynomials() {
+        let poly = |c: &[NumType]| Polynomial {
+            coef: c
+                .iter()
+                .map(|&x| FiniteField {
+                    char: 2,
+                    element: Element::PrimeField { element: x },
+                })
+                .collect(),
+        };
+        // the AES modulus is irreducible but x has order 51
+        let aes = poly(&[1, 1, 0, 1, 1, 0, 0, 0, 1]);
+        assert!(aes.is_irreducible());
+        assert!(!aes.is_primitive());
+        // x^8 + x^4 + x^3 + x^2 + 1
+        let primitive = poly(&[1, 0, 1, 1, 1, 0, 0, 0, 1]);
+        assert!(primitive.is_primitive());
+        assert_eq!(Polynomial::get_primitive_polynomial(2, 8), primitive);
+        // reducible: x^4 + 1 = (x + 1)^4
+        assert!(!poly(&[1, 0, 0, 0, 1]).is_primitive());
+
+        let f7 = |c: &[u8]| Polynomial {
+            coef: c.iter().map(|&x| F7(x)).collect(),
+        };
+        // x^2 + 1 is irreducible over F_7 but x^4 = 1
+        assert!(!f7(&[1, 0, 1]).is_primitive());
+        assert!(f7(&[3, 1, 1]).is_primitive());
     }
 }
@@ -0,0 +1,98 @@
+//! Log/antilog and Zech logarithm tables of a small GF(p^n).
+
+use crate::gf::GF;
+use crate::NumType;
+
+/// Largest field for which tables are built.
+pub(crate) const MAX_TABLE_ORDER: u128 = 1 << 16;
+
+// marks log(0) and undefined Zech logarithms
+const NONE: u32 = u32::MAX;
+
+/// Tables for a primitive element a of GF(q), with elements indexed by `GF::coef_to_index`.
+pub(crate) struct LogTables {
+    // exp[i] = index of a^i, for 0 <= i < q-1
+    exp: Vec<u32>,
+    // log[index of a^i] = i
+    log: Vec<u32>,
+    // a^zech[n] = 1 + a^n
+    zech: Vec<u32>,
+}
+
+impl LogTables {
+    /// Build the tables from the first primitive element found in index order, trying x first.
+    pub(crate) fn build(gf: &GF) -> LogTables {
+        let q = gf.order() as usize;
+        let candidates = std::iter::once(gf.char() as usize).chain(2..q);
+        let (exp, log) = candidates
+            .filter_map(|g| power_table(gf, g))
+            .next()
+            .expect("the multiplicative group of a finite field is cyclic");
+
+        let p = gf.char() as usize;
+        let zech = exp
+            .iter()
+            .map(|&e| {
+                // index of 1 + a^n: add 1 to the constant coefficient
+                let e = e as usize;
+                let one_plus = if e % p == p - 1 { e + 1 - p } else { e + 1 };
+                log[one_plus]
+            })
+            .collect();
+        LogTables { exp, log, zech }
+    }
+
+    pub(crate) fn log(&self, index: usize) -> Option<u32> {
+        match self.log[index] {
+            NONE => None,
+            l => Some(l),
+        }
+    }
+
+    pub(crate) fn exp(&self, i: u128) -> usize {
+        self.exp[(i % self.exp.len() as u128) as usize] as usize
+    }
+
+    pub(crate) fn zech(&self, n: u32) -> Option<u32> {
+        match self.zech[n as usize % self.zech.len()] {
+            NONE => None,
+            z => Some(z),
+        }
+    }
+
+    pub(crate) fn mul(&self, gf: &GF, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        match (self.log(gf.coef_to_index(f)), self.log(gf.coef_to_index(g))) {
+            (Some(a), Some(b)) => gf.index_to_coef(self.exp(a as u128 + b as u128)),
+            _ => vec![0],
+        }
+    }
+
+    /// f / g. Panics if g = 0.
+    pub(crate) fn div(&self, gf: &GF, f: &[NumType], g: &[NumType]) -> Vec<NumType> {
+        let order = self.exp.len() as u128;
+        let b = self.log(gf.coef_to_index(g)).expect("division by zero");
+        match self.log(gf.coef_to_index(f)) {
+            Some(a) => gf.index_to_coef(self.exp(a as u128 + order - b as u128)),
+            None => vec![0],
+        }
+    }
+}
+
+// exp and log tables of the powers of the element with the given index, if it is primitive
+fn power_table(gf: &GF, g: usize) -> Option<(Vec<u32>, Vec<u32>)> {
+    let q = gf.order() as usize;
+    let g = gf.index_to_coef(g);
+    let mut exp = Vec::with_capacity(q - 1);
+    let mut log = vec![NONE; q];
+    let mut current: Vec<NumType> = vec![1];
+    for i in 0..q - 1 {
+        let index = gf.coef_to_index(&current);
+        if log[index] != NONE {
+            return None;
+        }
+        log[index] = i as u32;
+        exp.push(index as u32);
+        current = gf.mul_coef(&current, &g);
+    }
+    Some((exp, log))
+}
@@ -0,0 +1,474 @@
+//! Prime fields with multi-limb moduli, multiplied in Montgomery form.
+
+use crate::Field;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops;
+
+/// Modulus of a `MontFp`, given as a type so that elements of different fields cannot be mixed.
+///
+/// `MODULUS` must be an odd prime, as little-endian 64-bit limbs.
+pub trait MontModulus<const N: usize>: 'static {
+    const MODULUS: [u64; N];
+}
+
+/// Element of the prime field F_p for a prime of up to 64 N bits, stored as N 64-bit limbs.
+///
+/// Multiplication is done in Montgomery form x R mod p with R = 2^(64N).
+/// ```
+/// use galois_field::*;
+/// // Curve25519, p = 2^255 - 19
+/// struct P25519;
+/// impl MontModulus<4> for P25519 {
+///     const MODULUS: [u64; 4] = [
+///         0xffffffffffffffed,
+///         0xffffffffffffffff,
+///         0xffffffffffffffff,
+///         0x7fffffffffffffff,
+///     ];
+/// }
+/// type F = MontFp<P25519, 4>;
+///
+/// let x = F::new(2);
+/// assert_eq!(x * x.inv(), F::new(1));
+/// // -1 = p - 1
+/// assert_eq!(-F::new(1), F::from_limbs([0xffffffffffffffec, !0, !0, 0x7fffffffffffffff]));
+/// // p = 5 mod 8, so 2 is not a square
+/// assert_eq!(x.legendre(), -1);
+/// ```
+pub struct MontFp<M: MontModulus<N>, const N: usize> {
+    // x R mod p
+    mont: [u64; N],
+    modulus: PhantomData<M>,
+}
+
+impl<M: MontModulus<N>, const N: usize> MontFp<M, N> {
+    // -p^(-1) mod 2^64
+    const INV: u64 = neg_inv(M::MODULUS[0]);
+    // R mod p, the Montgomery form of 1
+    const R: [u64; N] = pow2_mod(64 * N, &M::MODULUS);
+    // R^2 mod p, to convert into Montgomery form
+    const R2: [u64; N] = pow2_mod(128 * N, &M::MODULUS);
+    const MODULUS_IS_ODD: () = assert!(
+        M::MODULUS[0] & 1 == 1 && !(M::MODULUS[0] == 1 && is_zero(&M::MODULUS, 1)),
+        "the modulus of MontFp must be an odd prime"
+    );
+
+    fn from_mont(mont: [u64; N]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::MODULUS_IS_ODD;
+        MontFp {
+            mont,
+            modulus: PhantomData,
+        }
+    }
+
+    /// Element x mod p.
+    pub fn new(x: u64) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = x;
+        Self::from_limbs(limbs)
+    }
+
+    /// Element with the given little-endian limbs, reduced mod p.
+    pub fn from_limbs(limbs: [u64; N]) -> Self {
+        Self::from_mont(mont_mul(&limbs, &Self::R2, &M::MODULUS, Self::INV))
+    }
+
+    /// Representative in 0..p as little-endian limbs.
+    pub fn to_limbs(&self) -> [u64; N] {
+        let mut one = [0; N];
+        one[0] = 1;
+        mont_mul(&self.mont, &one, &M::MODULUS, Self::INV)
+    }
+
+    /// Legendre symbol: 1 if self is a nonzero square, -1 if it is not a square, 0 if it is 0.
+    ///
+    /// Computed by Euler's criterion x^((p-1)/2).
+    pub fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        // (p - 1) / 2 = p >> 1 since p is odd
+        let mut e = M::MODULUS;
+        for i in 0..N {
+            e[i] = (e[i] >> 1) | e.get(i + 1).map_or(0, |&h| h << 63);
+        }
+        if self.pow_big(&e).is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> Clone for MontFp<M, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> Copy for MontFp<M, N> {}
+
+impl<M: MontModulus<N>, const N: usize> PartialEq for MontFp<M, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mont == other.mont
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> Eq for MontFp<M, N> {}
+
+impl<M: MontModulus<N>, const N: usize> fmt::Debug for MontFp<M, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MontFp(0x")?;
+        for limb in self.to_limbs().iter().rev() {
+            write!(f, "{:016x}", limb)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> From<u64> for MontFp<M, N> {
+    fn from(x: u64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> ops::Add for MontFp<M, N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::from_mont(add_mod(&self.mont, &other.mont, &M::MODULUS))
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> ops::Sub for MontFp<M, N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::from_mont(sub_mod(&self.mont, &other.mont, &M::MODULUS))
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> ops::Mul for MontFp<M, N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::from_mont(mont_mul(&self.mont, &other.mont, &M::MODULUS, Self::INV))
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> ops::Div for MontFp<M, N> {
+    type Output = Self;
+    // As with `Fp`, dividing by 0 panics.
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "division by zero");
+        Self::from_mont(mont_mul(
+            &self.mont,
+            &other.inv().mont,
+            &M::MODULUS,
+            Self::INV,
+        ))
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> ops::Neg for MontFp<M, N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_mont(sub_mod(&[0; N], &self.mont, &M::MODULUS))
+    }
+}
+
+impl<M: MontModulus<N>, const N: usize> Field for MontFp<M, N> {
+    fn zero(&self) -> Self {
+        Self::from_mont([0; N])
+    }
+    fn one(&self) -> Self {
+        Self::from_mont(Self::R)
+    }
+    /// x^(p-2) by Fermat's little theorem.
+    fn inv(&self) -> Self {
+        assert!(!self.is_zero(), "0 has no inverse");
+        let mut two = [0; N];
+        two[0] = 2;
+        self.pow_big(&sub_mod(&M::MODULUS, &two, &M::MODULUS))
+    }
+    /// Panics if p does not fit in u64, see `characteristic_big`.
+    fn characteristic(&self) -> u64 {
+        assert!(
+            is_zero(&M::MODULUS, 1),
+            "characteristic does not fit in u64"
+        );
+        M::MODULUS[0]
+    }
+    /// Panics if p does not fit in u128, see `order_big`.
+    fn order(&self) -> u128 {
+        assert!(is_zero(&M::MODULUS, 2), "order does not fit in u128");
+        M::MODULUS[0] as u128 | (M::MODULUS.get(1).copied().unwrap_or(0) as u128) << 64
+    }
+    fn characteristic_big(&self) -> Vec<u64> {
+        M::MODULUS.to_vec()
+    }
+    fn order_big(&self) -> Vec<u64> {
+        M::MODULUS.to_vec()
+    }
+    fn is_zero(&self) -> bool {
+        self.mont == [0; N]
+    }
+    fn is_one(&self) -> bool {
+        self.mont == Self::R
+    }
+    fn frobenius(&self) -> Self {
+        *self
+    }
+}
+
+// the limbs from index `from` on are all 0
+const fn is_zero<const N: usize>(a: &[u64; N], from: usize) -> bool {
+    let mut i = from;
+    while i < N {
+        if a[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+// a - b and the borrow
+const fn sub_borrow<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], bool) {
+    let mut result = [0; N];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < N {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        result[i] = d;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+// a + b mod p for a, b < p
+const fn add_mod<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N]) -> [u64; N] {
+    let mut sum = [0; N];
+    let mut carry = false;
+    let mut i = 0;
+    while i < N {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        sum[i] = s;
+        carry = c1 || c2;
+        i += 1;
+    }
+    if carry || geq(&sum, p) {
+        sub_borrow(&sum, p).0
+    } else {
+        sum
+    }
+}
+
+// a - b mod p for a, b < p
+fn sub_mod<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N]) -> [u64; N] {
+    let (diff, borrow) = sub_borrow(a, b);
+    if !borrow {
+        return diff;
+    }
+    let mut result = [0; N];
+    let mut carry = 0u128;
+    for i in 0..N {
+        let s = diff[i] as u128 + p[i] as u128 + carry;
+        result[i] = s as u64;
+        carry = s >> 64;
+    }
+    result
+}
+
+// 2^k mod p by doubling
+const fn pow2_mod<const N: usize>(k: usize, p: &[u64; N]) -> [u64; N] {
+    let mut x = [0; N];
+    x[0] = 1;
+    let mut i = 0;
+    while i < k {
+        x = add_mod(&x, &x, p);
+        i += 1;
+    }
+    x
+}
+
+// -p0^(-1) mod 2^64 by Newton's iteration, each step doubling the correct bits
+const fn neg_inv(p0: u64) -> u64 {
+    let mut inv: u64 = 1;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+// a b R^(-1) mod p by coarsely integrated operand scanning; a < R and b < p
+fn mont_mul<const N: usize>(a: &[u64; N], b: &[u64; N], p: &[u64; N], inv: u64) -> [u64; N] {
+    // t has N + 2 words: t[0..N], hi, top
+    let mut t = [0u64; N];
+    let mut hi = 0u64;
+    for &bi in b {
+        // t += a * b_i
+        let mut carry = 0u128;
+        for j in 0..N {
+            let s = t[j] as u128 + a[j] as u128 * bi as u128 + carry;
+            t[j] = s as u64;
+            carry = s >> 64;
+        }
+        let s = hi as u128 + carry;
+        hi = s as u64;
+        let top = (s >> 64) as u64;
+
+        // t = (t + m p) / 2^64, which is exact for this m
+        let m = t[0].wrapping_mul(inv);
+        let mut carry = (t[0] as u128 + m as u128 * p[0] as u128) >> 64;
+        for j in 1..N {
+            let s = t[j] as u128 + m as u128 * p[j] as u128 + carry;
+            t[j - 1] = s as u64;
+            carry = s >> 64;
+        }
+        let s = hi as u128 + carry;
+        t[N - 1] = s as u64;
+        hi = top + (s >> 64) as u64;
+    }
+    if hi != 0 || geq(&t, p) {
+        sub_borrow(&t, p).0
+    } else {
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::P25519;
+    use crate::{Matrix, Polynomial};
+
+    // secp256k1, with the top bit set
+    struct Secp256k1;
+    impl MontModulus<4> for Secp256k1 {
+        const MODULUS: [u64; 4] = [
+            0xfffffffefffffc2f,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+        ];
+    }
+
+    // BN254 scalar field
+    struct Bn254;
+    impl MontModulus<4> for Bn254 {
+        const MODULUS: [u64; 4] = [
+            0x43e1f593f0000001,
+            0x2833e84879b97091,
+            0xb85045b68181585d,
+            0x30644e72e131a029,
+        ];
+    }
+
+    // 2^64 - 59, compared with u128 arithmetic
+    struct P64;
+    impl MontModulus<1> for P64 {
+        const MODULUS: [u64; 1] = [0xffffffffffffffc5];
+    }
+
+    #[test]
+    fn matches_u128_arithmetic() {
+        type F = MontFp<P64, 1>;
+        let p = P64::MODULUS[0] as u128;
+        for (a, b) in [
+            (1u64, 2u64),
+            (p as u64 - 1, p as u64 - 2),
+            (1 << 63, 3 << 62),
+        ] {
+            let (x, y) = (F::new(a), F::new(b));
+            let (a, b) = (a as u128, b as u128);
+            assert_eq!((x + y).to_limbs()[0] as u128, (a + b) % p);
+            assert_eq!((x - y).to_limbs()[0] as u128, (a + p - b) % p);
+            assert_eq!((x * y).to_limbs()[0] as u128, a * b % p);
+            assert_eq!(x * x.inv(), F::new(1));
+        }
+        assert_eq!(F::new(u64::MAX).to_limbs(), [58]);
+    }
+
+    #[test]
+    fn known_products() {
+        // a = 2^200 + 12345, b = 2^254 + 7
+        fn check<M: MontModulus<4>>(product: [u64; 4], inverse: [u64; 4]) {
+            let a = MontFp::<M, 4>::from_limbs([12345, 0, 0, 1 << 8]);
+            let b = MontFp::<M, 4>::from_limbs([7, 0, 0, 1 << 62]);
+            assert_eq!((a * b).to_limbs(), product);
+            assert_eq!(a.inv().to_limbs(), inverse);
+            assert_eq!(a / b * b, a);
+            assert_eq!((a * a).legendre(), 1);
+        }
+        check::<P25519>(
+            [0x31ba3, 0, 0, 0x4000000000001080],
+            [
+                0xbecc34f47b31e357,
+                0x3bb76fda109c66c2,
+                0x1a3eb72316725db3,
+                0x71102662a8472176,
+            ],
+        );
+        check::<Secp256k1>(
+            [0xc0e002f52fd, 0, 0, 0x400000400000fb40],
+            [
+                0xab672a4cc9584dff,
+                0x7af1817e47cee613,
+                0x171d9ba80d58e622,
+                0xe69162797174675b,
+            ],
+        );
+    }
+
+    #[test]
+    fn legendre() {
+        type F = MontFp<Bn254, 4>;
+        // r - 1 = 2^28 * odd, so 5 is a non-residue (the generator used for the FFT domain)
+        assert_eq!(F::new(5).legendre(), -1);
+        assert_eq!(F::new(4).legendre(), 1);
+        assert_eq!(F::new(0).legendre(), 0);
+        assert_eq!((-F::new(1)).legendre(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        type F = MontFp<P25519, 4>;
+        let _ = F::new(1) / F::new(0);
+    }
+
+    #[test]
+    fn polynomial_and_matrix() {
+        type F = MontFp<P25519, 4>;
+        let poly = |c: &[u64]| Polynomial {
+            coef: c.iter().map(|&x| F::new(x)).collect(),
+        };
+        // (x + 2)(x + 3) = x^2 + 5x + 6
+        assert_eq!(poly(&[6, 5, 1]) / poly(&[2, 1]), poly(&[3, 1]));
+        assert_eq!(poly(&[6, 5, 1]).gcd(poly(&[4, 4, 1])), poly(&[2, 1]));
+
+        let m = Matrix {
+            element: vec![vec![F::new(2), F::new(4)], vec![F::new(3), F::new(5)]],
+        };
+        let expected = Matrix {
+            element: vec![vec![F::new(1), F::new(0)], vec![F::new(0), F::new(1)]],
+        };
+        assert_eq!(m.sweep_method(), expected);
+    }
+}
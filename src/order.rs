@@ -0,0 +1,142 @@
+//! Multiplicative orders and generators of the multiplicative group of F_p or GF(p^n).
+
+use crate::integer::{factorize, gcd};
+use crate::{Field, FiniteField, GF};
+
+impl FiniteField {
+    /// The least k >= 1 with a^k = 1, found by removing prime factors from q - 1.
+    ///
+    /// Panics if a = 0.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// assert_eq!(gf.gen().multiplicative_order(), 15);
+    /// assert_eq!(gf.gen().pow(5).multiplicative_order(), 3);
+    /// assert_eq!(gf.one().multiplicative_order(), 1);
+    /// ```
+    pub fn multiplicative_order(&self) -> u128 {
+        self.multiplicative_order_with(&factorize(Field::order(self) - 1))
+    }
+
+    // the order of a, given the prime factors of q - 1
+    pub(crate) fn multiplicative_order_with(&self, factors: &[(u128, u32)]) -> u128 {
+        assert!(!self.is_0(), "0 has no multiplicative order");
+        let mut order = Field::order(self) - 1;
+        for &(r, _) in factors {
+            while order.is_multiple_of(r) && self.pow(order / r).is_1() {
+                order /= r;
+            }
+        }
+        order
+    }
+
+    /// Determine if a generates the multiplicative group, i.e. a^((q-1)/r) != 1 for every prime r dividing q - 1.
+    pub fn is_generator(&self) -> bool {
+        !self.is_0() && self.is_generator_with(&factorize(Field::order(self) - 1))
+    }
+
+    // is_generator for a nonzero a, given the prime factors of q - 1
+    fn is_generator_with(&self, factors: &[(u128, u32)]) -> bool {
+        let order = Field::order(self) - 1;
+        factors.iter().all(|&(r, _)| !self.pow(order / r).is_1())
+    }
+
+    /// The first generator of the multiplicative group of `field`, counting elements with the
+    /// coefficients as base p digits.
+    ///
+    /// For n > 1 the elements of F_p are skipped, since they never generate.
+    /// ```
+    /// use galois_field::*;
+    /// // 3 is the least primitive root mod 7
+    /// let g = FiniteField::primitive_element(&GF::new(7, 1));
+    /// assert_eq!(g, GF::new(7, 1).elem(&[3]));
+    /// ```
+    pub fn primitive_element(field: &GF) -> FiniteField {
+        let start = if field.degree() == 1 {
+            1
+        } else {
+            field.char() as usize
+        };
+        let factors = factorize(field.order() - 1);
+        (start..)
+            .map(|i| field.elem(&field.index_to_coef(i)))
+            .find(|a| a.is_generator_with(&factors))
+            .unwrap()
+    }
+
+    /// Every generator of the multiplicative group of `field`, as g^k for k coprime to q - 1
+    /// in increasing order, where g is `primitive_element(field)`.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// // phi(15) = 8
+    /// assert_eq!(FiniteField::generators(&gf).count(), 8);
+    /// ```
+    pub fn generators(field: &GF) -> impl Iterator<Item = FiniteField> {
+        let g = FiniteField::primitive_element(field);
+        let order = field.order() - 1;
+        (1..=order)
+            .filter(move |&k| gcd(k, order) == 1)
+            .map(move |k| g.pow(k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integer::euler_phi;
+    use crate::testing::elements;
+    use crate::Polynomial;
+
+    #[test]
+    fn orders_by_brute_force() {
+        for gf in [
+            GF::new(2, 4),
+            GF::new(3, 2),
+            GF::new(7, 1),
+            GF::new(13, 1),
+            GF::new(2, 6),
+        ] {
+            let q = gf.order();
+            let mut generators = Vec::new();
+            for a in elements(&gf).into_iter().skip(1) {
+                let mut k = 1;
+                let mut power = a.clone();
+                while !power.is_1() {
+                    power = power * a.clone();
+                    k += 1;
+                }
+                assert_eq!(a.multiplicative_order(), k);
+                assert_eq!(a.is_generator(), k == q - 1);
+                if k == q - 1 {
+                    generators.push(a);
+                }
+            }
+            assert_eq!(generators.len() as u128, euler_phi(q - 1));
+            assert_eq!(FiniteField::primitive_element(&gf), generators[0]);
+            let mut all: Vec<FiniteField> = FiniteField::generators(&gf).collect();
+            assert_eq!(all.len(), generators.len());
+            all.retain(|a| generators.contains(a));
+            assert_eq!(all.len(), generators.len());
+        }
+    }
+
+    #[test]
+    fn large_characteristic() {
+        // GF(p^2) for p = 2^61 - 1, where q - 1 = (p - 1)(p + 1) = 2^62 (2^60 - 1)
+        let p = (1u64 << 61) - 1;
+        let fp = FiniteField {
+            char: p,
+            element: crate::Element::PrimeField { element: 0 },
+        };
+        let gf = GF::with_modulus(Polynomial {
+            coef: vec![fp.get_1(), fp.clone(), fp.get_1()],
+        });
+        let g = FiniteField::primitive_element(&gf);
+        assert!(g.is_generator());
+        assert_eq!(g.multiplicative_order(), gf.order() - 1);
+        // x^2 = -1, so x has order 4
+        assert_eq!(gf.gen().multiplicative_order(), 4);
+        assert!(!g.pow(3).is_generator());
+    }
+}
@@ -0,0 +1,193 @@
+//! Roots of quadratics over GF(2^n), where x^2 + x = a takes the place of the quadratic formula.
+
+use crate::{Element, FiniteField};
+
+impl FiniteField {
+    /// Half-trace a + a^4 + a^16 + ... + a^(4^((n-1)/2)) in GF(2^n) for odd n.
+    ///
+    /// It solves x^2 + x = a + Tr(a). Panics unless the characteristic is 2 and n is odd.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 5);
+    /// let a = gf.elem(&[1, 1, 0, 1]);
+    /// assert!(a.trace().is_0());
+    /// let h = a.half_trace();
+    /// assert_eq!(h.clone() * h.clone() + h, a);
+    /// ```
+    pub fn half_trace(&self) -> FiniteField {
+        let n = self.binary_degree();
+        assert!(n % 2 == 1, "the half-trace needs odd n, not {}", n);
+        let mut power = self.clone();
+        let mut h = self.clone();
+        for _ in 0..(n - 1) / 2 {
+            power = power.frobenius().frobenius();
+            h = h + power.clone();
+        }
+        h
+    }
+
+    /// The two roots (x, x + 1) of x^2 + x = a in GF(2^n), or None if Tr(a) != 0.
+    ///
+    /// For odd n x is the half-trace of a; otherwise x = sum of (d + d^2 + ... + d^(2^(i-1))) a^(2^i)
+    /// over 0 < i < n for a basis element d of trace 1, cached on the field. Panics unless the
+    /// characteristic is 2.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// // x^2 + x = 1 has the roots of x^2 + x + 1, which lie in GF(4) inside GF(16)
+    /// let (x, y) = gf.one().solve_x2_plus_x().unwrap();
+    /// assert_eq!(x.clone() * x.clone() + x.clone(), gf.one());
+    /// assert_eq!(x + gf.one(), y);
+    /// // Tr(x) = 0 and Tr(x^3) = 1 for x^4 + x + 1
+    /// assert!(gf.gen().solve_x2_plus_x().is_some());
+    /// assert!(gf.gen().pow(3).solve_x2_plus_x().is_none());
+    /// ```
+    pub fn solve_x2_plus_x(&self) -> Option<(FiniteField, FiniteField)> {
+        let n = self.binary_degree();
+        if !self.trace().is_0() {
+            return None;
+        }
+        let x = if n % 2 == 1 {
+            self.half_trace()
+        } else {
+            let field = match &self.element {
+                Element::GaloisField { field, .. } => field,
+                Element::PrimeField { .. } => unreachable!(),
+            };
+            let d = field.elem(field.trace_one_coef());
+            let mut x = self.get_0();
+            let mut a_i = self.clone();
+            let mut d_i = d;
+            let mut s = self.get_0();
+            for _ in 1..n {
+                s = s + d_i.clone();
+                d_i = d_i.frobenius();
+                a_i = a_i.frobenius();
+                x = x + s.clone() * a_i.clone();
+            }
+            x
+        };
+        let y = x.clone() + self.get_1();
+        Some((x, y))
+    }
+
+    /// The two roots of a x^2 + b x + c = 0 in GF(2^n), or None if it has no root there.
+    ///
+    /// The roots coincide when b = 0. Otherwise x = (b/a) y with y^2 + y = ac/b^2, which is
+    /// solved by `solve_x2_plus_x`. Panics if a = 0 or the characteristic is not 2.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 8);
+    /// let (a, b) = (gf.elem(&[1, 0, 1]), gf.elem(&[0, 1, 1, 1]));
+    /// // (x - a)(x - b) = x^2 + (a + b) x + ab
+    /// let (sum, product) = (a.clone() + b.clone(), a.clone() * b.clone());
+    /// let (r, s) = FiniteField::solve_quadratic(&gf.one(), &sum, &product).unwrap();
+    /// assert!((r.clone(), s.clone()) == (a.clone(), b.clone()) || (r, s) == (b, a));
+    /// ```
+    pub fn solve_quadratic(
+        a: &FiniteField,
+        b: &FiniteField,
+        c: &FiniteField,
+    ) -> Option<(FiniteField, FiniteField)> {
+        assert_eq!(
+            a.char, 2,
+            "characteristic {} is not 2; use sqrt for the quadratic formula",
+            a.char
+        );
+        assert!(!a.is_0(), "a x^2 + b x + c with a = 0 is not a quadratic");
+        if b.is_0() {
+            // x^2 = c/a
+            return (c.clone() / a.clone()).sqrt();
+        }
+        let ba = b.clone() / a.clone();
+        let (y, z) = (a.clone() * c.clone() / (b.clone() * b.clone())).solve_x2_plus_x()?;
+        Some((ba.clone() * y, ba * z))
+    }
+
+    // n for an element of GF(2^n)
+    fn binary_degree(&self) -> usize {
+        assert!(
+            self.char == 2,
+            "characteristic {} is not 2; use sqrt for the quadratic formula",
+            self.char
+        );
+        match &self.element {
+            Element::PrimeField { .. } => 1,
+            Element::GaloisField { field, .. } => field.degree(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::elements;
+    use crate::{FiniteField, GF};
+
+    #[test]
+    fn artin_schreier_by_brute_force() {
+        for n in 1..=7 {
+            let gf = GF::new(2, n);
+            let elements = elements(&gf);
+            for a in &elements {
+                let roots: Vec<&FiniteField> = elements
+                    .iter()
+                    .filter(|x| (*x).clone() * (*x).clone() + (*x).clone() == *a)
+                    .collect();
+                match a.solve_x2_plus_x() {
+                    Some((x, y)) => {
+                        assert_eq!(roots.len(), 2);
+                        assert!(roots.contains(&&x) && roots.contains(&&y));
+                        assert_ne!(x, y);
+                    }
+                    None => {
+                        assert!(roots.is_empty());
+                        assert!(a.trace().is_1());
+                    }
+                }
+                if n % 2 == 1 {
+                    let h = a.half_trace();
+                    assert_eq!(h.clone() * h.clone() + h, a.clone() + gf.embed(&a.trace()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quadratics_by_brute_force() {
+        let gf = GF::new(2, 4);
+        let elements = elements(&gf);
+        for a in elements.iter().skip(1).step_by(4) {
+            for b in &elements {
+                for c in &elements {
+                    let roots: Vec<&FiniteField> = elements
+                        .iter()
+                        .filter(|x| {
+                            (a.clone() * (*x).clone() + b.clone()) * (*x).clone() + c.clone()
+                                == gf.zero()
+                        })
+                        .collect();
+                    match FiniteField::solve_quadratic(a, b, c) {
+                        Some((r, s)) => {
+                            assert!(roots.contains(&&r) && roots.contains(&&s));
+                            assert_eq!(roots.len(), if r == s { 1 } else { 2 });
+                        }
+                        None => assert!(roots.is_empty()),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "characteristic 3 is not 2")]
+    fn odd_characteristic() {
+        GF::new(3, 2).gen().solve_x2_plus_x();
+    }
+
+    #[test]
+    #[should_panic(expected = "characteristic 5 is not 2")]
+    fn odd_characteristic_quadratic() {
+        let gf = GF::new(5, 1);
+        FiniteField::solve_quadratic(&gf.one(), &gf.one(), &gf.one());
+    }
+}
@@ -0,0 +1,219 @@
+//! Quadratic residuosity and square roots in F_p and GF(p^n), by Tonelli-Shanks in odd
+//! characteristic and by the inverse of Frobenius in characteristic 2.
+
+use crate::{Element, Field, FiniteField};
+
+impl FiniteField {
+    /// The quadratic character a^((q-1)/2): 1 for a nonzero square, -1 for a non-square and 0 for 0.
+    ///
+    /// In F_p this is the Legendre symbol (a/p). In characteristic 2 every element is a square.
+    /// ```
+    /// use galois_field::*;
+    /// let f7 = GF::new(7, 1);
+    /// // the squares mod 7 are 1, 2 and 4
+    /// assert_eq!(f7.elem(&[2]).legendre(), 1);
+    /// assert_eq!(f7.elem(&[3]).legendre(), -1);
+    /// assert_eq!(f7.zero().legendre(), 0);
+    /// ```
+    pub fn legendre(&self) -> i8 {
+        if self.is_0() {
+            0
+        } else if self.char == 2 || self.pow((Field::order(self) - 1) / 2).is_1() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Determine if a = b^2 for some b in the field. 0 is a square.
+    pub fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// The two square roots (r, -r) of a, or None if a is not a square.
+    ///
+    /// The roots coincide for a = 0 and in characteristic 2, where r = a^(2^(n-1)).
+    /// Otherwise r is found by Tonelli-Shanks, which works in any GF(q) with q odd.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(3, 2);
+    /// let a = gf.elem(&[1, 2]);
+    /// let (r, s) = (a.clone() * a.clone()).sqrt().unwrap();
+    /// assert!(r == a || s == a);
+    /// assert_eq!(r.clone() + s, gf.zero());
+    /// // x generates GF(9)^*, so it is not a square
+    /// assert_eq!(gf.gen().sqrt(), None);
+    /// ```
+    pub fn sqrt(&self) -> Option<(FiniteField, FiniteField)> {
+        if self.char == 2 {
+            let n = match &self.element {
+                Element::PrimeField { .. } => 1,
+                Element::GaloisField { field, .. } => field.degree(),
+            };
+            // Frobenius has order n, so its inverse is the (n-1)-th power
+            let mut r = self.clone();
+            for _ in 1..n {
+                r = r.frobenius();
+            }
+            return Some((r.clone(), r));
+        }
+        match self.legendre() {
+            0 => Some((self.get_0(), self.get_0())),
+            -1 => None,
+            _ => {
+                let r = self.tonelli_shanks();
+                Some((r.clone(), -r))
+            }
+        }
+    }
+
+    // a square root of a nonzero square in odd characteristic
+    fn tonelli_shanks(&self) -> FiniteField {
+        let q = Field::order(self);
+        // q - 1 = 2^s t with t odd
+        let s = (q - 1).trailing_zeros();
+        let t = (q - 1) >> s;
+        if s == 1 {
+            return self.pow(q.div_ceil(4));
+        }
+        // z^t generates the 2-Sylow subgroup for a non-square z
+        let mut c = self.non_square().pow(t);
+        let mut r = self.pow(t.div_ceil(2));
+        // a^t lies in the 2-Sylow subgroup; r^2 = a * b keeps holding while b is driven to 1
+        let mut b = self.pow(t);
+        let mut m = s;
+        while !b.is_1() {
+            // the least i with b^(2^i) = 1
+            let mut i = 0;
+            let mut b2 = b.clone();
+            while !b2.is_1() {
+                b2 = b2.clone() * b2;
+                i += 1;
+            }
+            let mut d = c;
+            for _ in 0..m - i - 1 {
+                d = d.clone() * d;
+            }
+            c = d.clone() * d.clone();
+            r = r * d;
+            b = b * c.clone();
+            m = i;
+        }
+        r
+    }
+
+    // the first non-square, counting elements with the coefficients as base p digits;
+    // in GF(p^n) it is cached on the field
+    fn non_square(&self) -> FiniteField {
+        match &self.element {
+            Element::PrimeField { .. } => (2..)
+                .map(|i| FiniteField {
+                    char: self.char,
+                    element: Element::PrimeField { element: i },
+                })
+                .find(|z| z.legendre() == -1)
+                .unwrap(),
+            Element::GaloisField { field, .. } => field.elem(field.non_square_coef()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::elements;
+    use crate::{Field, FiniteField, Fp, Polynomial, GF};
+
+    #[test]
+    fn roots_by_brute_force() {
+        for gf in [
+            GF::new(7, 1),
+            GF::new(13, 1),
+            GF::new(17, 1),
+            GF::new(3, 3),
+            GF::new(5, 2),
+            GF::new(3, 4),
+            GF::new(2, 5),
+            GF::new(2, 1),
+        ] {
+            let elements = elements(&gf);
+            let squares: Vec<FiniteField> =
+                elements.iter().map(|b| b.clone() * b.clone()).collect();
+            let mut nonzero_squares = 0;
+            for a in &elements {
+                let square = squares.contains(a);
+                assert_eq!(a.is_square(), square);
+                match a.sqrt() {
+                    Some((r, s)) => {
+                        assert!(square);
+                        assert_eq!(r.clone() * r.clone(), *a);
+                        assert_eq!(r + s, gf.zero());
+                    }
+                    None => assert!(!square),
+                }
+                if square && !a.is_0() {
+                    nonzero_squares += 1;
+                }
+            }
+            // half of the units are squares in odd characteristic, all in characteristic 2
+            let units = gf.order() - 1;
+            let expected = if gf.char() == 2 { units } else { units / 2 };
+            assert_eq!(nonzero_squares, expected);
+        }
+    }
+
+    // Euler's criterion a^((P-1)/2) computed in Fp
+    fn euler_criterion<const P: u64>(a: u64) -> i8 {
+        let x = Fp::<P>::new(a).pow((P as u128 - 1) / 2);
+        if x.is_zero() {
+            0
+        } else if x.is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    #[test]
+    fn legendre_matches_fp() {
+        let f13 = GF::new(13, 1);
+        for a in 0..13 {
+            assert_eq!(f13.elem(&[a]).legendre(), euler_criterion::<13>(a as u64));
+        }
+        let f17 = GF::new(17, 1);
+        for a in 0..17 {
+            assert_eq!(f17.elem(&[a]).legendre(), euler_criterion::<17>(a as u64));
+        }
+        const P: u64 = (1 << 61) - 1;
+        let fp = GF::new(P, 1);
+        for a in [2, 3, 5, 7, 123456789012345, P - 1] {
+            assert_eq!(fp.elem(&[a as i64]).legendre(), euler_criterion::<P>(a));
+        }
+    }
+
+    #[test]
+    fn large_characteristic() {
+        // p = 2^61 - 1 = 3 mod 4 and p^2 - 1 = 2^62 (2^60 - 1), which exercises Tonelli-Shanks
+        let p = (1u64 << 61) - 1;
+        let fp = GF::new(p, 1);
+        let a = fp.elem(&[123456789012345]);
+        let (r, _) = (a.clone() * a.clone()).sqrt().unwrap();
+        assert!(r == a || r == -a.clone());
+        assert_eq!(fp.elem(&[-1]).legendre(), -1);
+        // p = 7 2^20 + 1, so the 2-Sylow subgroup is large
+        let f = GF::new(7340033, 1);
+        for x in [3, 5, 1234567] {
+            let a = f.elem(&[x]);
+            let (r, s) = (a.clone() * a.clone()).sqrt().unwrap();
+            assert!(r == a || s == a);
+        }
+        // GF(p^2) with x^2 + 1
+        let gf = GF::with_modulus(Polynomial {
+            coef: vec![fp.one(), fp.zero(), fp.one()],
+        });
+        let a = gf.elem(&[98765, 43210]);
+        let (r, s) = (a.clone() * a.clone()).sqrt().unwrap();
+        assert!(r == a || s == a);
+        // every element of F_p is a square in GF(p^2)
+        assert!(gf.elem(&[3]).is_square());
+    }
+}
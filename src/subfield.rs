@@ -0,0 +1,412 @@
+//! Embeddings of GF(p^m) into GF(p^n) for m | n, the projection back from the image, and
+//! isomorphisms between two moduli of the same degree.
+
+use crate::{Element, FiniteField, Matrix, NumType, Polynomial, GF};
+
+/// The field homomorphism GF(p^m) -> GF(p^n), m | n, that sends the generator x of the
+/// smaller field to a root of its modulus in the larger one.
+///
+/// The map is F_p-linear, so it is kept as the images of 1, x, ..., x^(m-1) together with a
+/// left inverse on the image.
+/// ```
+/// use galois_field::*;
+/// let small = GF::new(2, 4);
+/// let large = GF::new(2, 8);
+/// let e = Embedding::new(&small, &large);
+/// let (a, b) = (small.gen(), small.elem(&[1, 0, 1]));
+/// assert_eq!(e.apply(&(a.clone() * b.clone())), e.apply(&a) * e.apply(&b));
+/// assert!(large.is_in_subfield(&e.apply(&a), 4));
+/// assert_eq!(e.project(&e.apply(&b)), Some(b));
+/// assert_eq!(e.project(&large.gen()), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Embedding {
+    from: GF,
+    to: GF,
+    // images of 1, x, ..., x^(m-1), each with n coefficients in 0..p
+    basis: Vec<Vec<NumType>>,
+    // m x n matrix over F_p whose product with the image of a is a
+    left_inverse: Vec<Vec<NumType>>,
+    // (n - m) x n matrix over F_p whose kernel is the image
+    cokernel: Vec<Vec<NumType>>,
+}
+
+impl Embedding {
+    /// The embedding of `from` = GF(p^m) into `to` = GF(p^n).
+    ///
+    /// Panics unless both have the same characteristic and m divides n.
+    pub fn new(from: &GF, to: &GF) -> Embedding {
+        let (m, n) = (from.degree(), to.degree());
+        assert!(
+            from.char() == to.char() && n.is_multiple_of(m),
+            "{:?} is not a subfield of {:?}",
+            from,
+            to
+        );
+        let p = to.char() as NumType;
+        let f = Polynomial {
+            coef: from.modulus().coef.iter().map(|c| to.embed(c)).collect(),
+        };
+        let root = find_root(f, to);
+
+        let mut basis = Vec::with_capacity(m);
+        let mut power = to.one();
+        for _ in 0..m {
+            let mut coef = to.coef_of(&power);
+            coef.resize(n, 0);
+            basis.push(coef);
+            power = power * root.clone();
+        }
+
+        // row reduce [M | I] for the n x m matrix M with the basis as columns
+        let c = |e: NumType| FiniteField {
+            char: to.char(),
+            element: Element::PrimeField { element: e },
+        };
+        let augmented = Matrix {
+            element: (0..n)
+                .map(|i| {
+                    let left = basis.iter().map(|b| c(b[i]));
+                    let right = (0..n).map(|j| c((i == j) as NumType));
+                    left.chain(right).collect()
+                })
+                .collect(),
+        };
+        let reduced: Vec<Vec<NumType>> = augmented
+            .sweep_method()
+            .element
+            .into_iter()
+            .map(|row| {
+                row[m..]
+                    .iter()
+                    .map(|x| match x.element {
+                        Element::PrimeField { element } => element.rem_euclid(p),
+                        Element::GaloisField { .. } => unreachable!(),
+                    })
+                    .collect()
+            })
+            .collect();
+        // M has full column rank, so the first m rows have the pivots
+        let (left_inverse, cokernel) = reduced.split_at(m);
+        Embedding {
+            from: from.clone(),
+            to: to.clone(),
+            basis,
+            left_inverse: left_inverse.to_vec(),
+            cokernel: cokernel.to_vec(),
+        }
+    }
+
+    /// The smaller field GF(p^m).
+    pub fn from(&self) -> &GF {
+        &self.from
+    }
+
+    /// The larger field GF(p^n).
+    pub fn to(&self) -> &GF {
+        &self.to
+    }
+
+    /// Image of an element of GF(p^m) in GF(p^n).
+    pub fn apply(&self, a: &FiniteField) -> FiniteField {
+        let p = self.to.char() as i128;
+        let mut coef = vec![0i128; self.to.degree()];
+        for (&a, b) in self.from.coef_of(a).iter().zip(&self.basis) {
+            for (c, &b) in coef.iter_mut().zip(b) {
+                *c = (*c + a as i128 * b as i128) % p;
+            }
+        }
+        self.to
+            .elem(&coef.into_iter().map(|c| c as NumType).collect::<Vec<_>>())
+    }
+
+    /// The element of GF(p^m) mapped to b, or None if b is not in the image.
+    pub fn project(&self, b: &FiniteField) -> Option<FiniteField> {
+        let mut b = self.to.coef_of(b);
+        b.resize(self.to.degree(), 0);
+        let p = self.to.char() as NumType;
+        if self.cokernel.iter().any(|row| dot(row, &b, p) != 0) {
+            return None;
+        }
+        let a: Vec<NumType> = self
+            .left_inverse
+            .iter()
+            .map(|row| dot(row, &b, p))
+            .collect();
+        Some(self.from.elem(&a))
+    }
+}
+
+/// An isomorphism between two representations of GF(p^n), i.e. GF(p)[x] / (f) and GF(p)[y] / (g)
+/// for irreducible f and g of the same degree n.
+///
+/// It sends x to a root of f in the second field. Both directions are kept as n x n matrices over F_p
+/// acting on coefficient vectors.
+/// ```
+/// use galois_field::*;
+/// let gf2 = GF::new(2, 1);
+/// let poly = |c: &[i64]| Polynomial { coef: c.iter().map(|&x| gf2.elem(&[x])).collect() };
+/// // AES's x^8 + x^4 + x^3 + x + 1 and the crate's primitive polynomial
+/// let aes = GF::with_modulus(poly(&[1, 1, 0, 1, 1, 0, 0, 0, 1]));
+/// let crate_gf = GF::new(2, 8);
+/// let iso = Isomorphism::new(&aes, &crate_gf);
+/// let (a, b) = (aes.elem(&[1, 1, 0, 1]), aes.elem(&[0, 0, 1, 0, 0, 0, 0, 1]));
+/// assert_eq!(iso.apply(&(a.clone() * b.clone())), iso.apply(&a) * iso.apply(&b));
+/// assert_eq!(iso.inverse().apply(&iso.apply(&a)), a);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Isomorphism {
+    from: GF,
+    to: GF,
+    // coefficients of the image of a are forward * (coefficients of a), entries in 0..p
+    forward: Vec<Vec<NumType>>,
+    backward: Vec<Vec<NumType>>,
+}
+
+impl Isomorphism {
+    /// The isomorphism from `from` to `to`.
+    ///
+    /// Panics unless both have the same characteristic and degree.
+    pub fn new(from: &GF, to: &GF) -> Isomorphism {
+        assert!(
+            from.degree() == to.degree(),
+            "{:?} and {:?} have different degrees",
+            from,
+            to
+        );
+        let embedding = Embedding::new(from, to);
+        let n = to.degree();
+        let forward = (0..n)
+            .map(|i| embedding.basis.iter().map(|b| b[i]).collect())
+            .collect();
+        Isomorphism {
+            from: from.clone(),
+            to: to.clone(),
+            forward,
+            backward: embedding.left_inverse,
+        }
+    }
+
+    /// The field that the isomorphism maps from.
+    pub fn from(&self) -> &GF {
+        &self.from
+    }
+
+    /// The field that the isomorphism maps to.
+    pub fn to(&self) -> &GF {
+        &self.to
+    }
+
+    /// Image of an element of `from` in `to`.
+    pub fn apply(&self, a: &FiniteField) -> FiniteField {
+        let mut a = self.from.coef_of(a);
+        a.resize(self.from.degree(), 0);
+        let p = self.from.char() as NumType;
+        let coef: Vec<NumType> = self.forward.iter().map(|row| dot(row, &a, p)).collect();
+        self.to.elem(&coef)
+    }
+
+    /// The inverse isomorphism from `to` to `from`.
+    pub fn inverse(&self) -> Isomorphism {
+        Isomorphism {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            forward: self.backward.clone(),
+            backward: self.forward.clone(),
+        }
+    }
+
+    /// The matrix over F_p whose column j holds the coefficients of the image of x^j.
+    pub fn matrix(&self) -> Matrix {
+        let p = self.from.char();
+        Matrix {
+            element: self
+                .forward
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&e| FiniteField {
+                            char: p,
+                            element: Element::PrimeField { element: e },
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+// sum of row[i] * v[i] mod p
+fn dot(row: &[NumType], v: &[NumType], p: NumType) -> NumType {
+    let p = p as i128;
+    let terms = row.iter().zip(v).map(|(&r, &x)| r as i128 * x as i128 % p);
+    terms.fold(0, |acc, x| (acc + x) % p) as NumType
+}
+
+// a root in `to` of f, a polynomial over `to` that splits into distinct linear factors,
+// by splitting f with gcds against (x + d)^((q-1)/2) - 1, or the trace of dx in characteristic 2
+fn find_root(f: Polynomial, to: &GF) -> FiniteField {
+    let x = Polynomial {
+        coef: vec![to.zero(), to.one()],
+    };
+    let mut g = f;
+    let mut candidates = (0..).map(|i| to.elem(&to.index_to_coef(i)));
+    while g.coef.len() > 2 {
+        let d = candidates.next().unwrap();
+        let h = if to.char() == 2 {
+            let dx = Polynomial {
+                coef: vec![to.zero(), d],
+            } % g.clone();
+            let mut power = dx.clone();
+            let mut trace = dx;
+            for _ in 1..to.degree() {
+                power = (power.clone() * power) % g.clone();
+                trace = trace + power.clone();
+            }
+            trace
+        } else {
+            let shifted = x.clone()
+                + Polynomial {
+                    coef: vec![d.clone()],
+                };
+            shifted.pow_mod((to.order() - 1) / 2, &g)
+                - Polynomial {
+                    coef: vec![to.one()],
+                }
+        };
+        let common = g.gcd(h);
+        let degree = common.coef.len() - 1;
+        if degree > 0 && degree < g.coef.len() - 1 {
+            let rest = g.clone() / common.clone();
+            g = if common.coef.len() <= rest.coef.len() {
+                common
+            } else {
+                rest
+            };
+        }
+    }
+    -(g.coef[0].clone() / g.coef[1].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::elements;
+    use crate::Field;
+
+    fn check(from: &GF, to: &GF) {
+        let e = Embedding::new(from, to);
+        let q = from.order() as usize;
+        let elems = elements(from);
+        let images: Vec<FiniteField> = elems.iter().map(|a| e.apply(a)).collect();
+        for (a, image) in elems.iter().zip(&images) {
+            assert!(to.is_in_subfield(image, from.degree()));
+            assert_eq!(e.project(image).as_ref(), Some(a));
+            if !a.is_zero() {
+                assert_eq!(e.apply(&a.inv()), image.inv());
+            }
+        }
+        for (a, image_a) in elems.iter().zip(&images).step_by(3) {
+            for (b, image_b) in elems.iter().zip(&images).step_by(2) {
+                assert_eq!(
+                    e.apply(&(a.clone() + b.clone())),
+                    image_a.clone() + image_b.clone()
+                );
+                assert_eq!(
+                    e.apply(&(a.clone() * b.clone())),
+                    image_a.clone() * image_b.clone()
+                );
+            }
+        }
+        // exactly the image lies in the subfield
+        if to.order() <= 1 << 10 {
+            let subfield = elements(to)
+                .into_iter()
+                .filter(|b| {
+                    let in_subfield = to.is_in_subfield(b, from.degree());
+                    assert_eq!(e.project(b).is_some(), in_subfield);
+                    in_subfield
+                })
+                .count();
+            assert_eq!(subfield, q);
+        }
+    }
+
+    #[test]
+    fn embeddings() {
+        check(&GF::new(2, 4), &GF::new(2, 8));
+        check(&GF::new(2, 2), &GF::new(2, 6));
+        check(&GF::new(2, 3), &GF::new(2, 6));
+        check(&GF::new(2, 1), &GF::new(2, 5));
+        check(&GF::new(3, 2), &GF::new(3, 4));
+        check(&GF::new(5, 1), &GF::new(5, 3));
+        check(&GF::new(7, 3), &GF::new(7, 3));
+    }
+
+    #[test]
+    fn isomorphisms() {
+        let poly = |p: u64, c: &[i64]| {
+            let gf = GF::new(p, 1);
+            Polynomial {
+                coef: c.iter().map(|&x| gf.elem(&[x])).collect(),
+            }
+        };
+        for (a, b) in [
+            // AES and the crate's primitive polynomial
+            (
+                GF::with_modulus(poly(2, &[1, 1, 0, 1, 1, 0, 0, 0, 1])),
+                GF::new(2, 8),
+            ),
+            // x^3 + 2x + 1 and x^3 + 2x^2 + 1 over F_3
+            (
+                GF::with_modulus(poly(3, &[1, 2, 0, 1])),
+                GF::with_modulus(poly(3, &[1, 0, 2, 1])),
+            ),
+        ] {
+            let iso = Isomorphism::new(&a, &b);
+            let inv = iso.inverse();
+            let n = a.degree();
+            let identity = Matrix {
+                element: (0..n)
+                    .map(|i| {
+                        (0..n)
+                            .map(|j| GF::new(a.char(), 1).elem(&[(i == j) as i64]))
+                            .collect()
+                    })
+                    .collect(),
+            };
+            assert_eq!(iso.matrix() * inv.matrix(), identity);
+            let elems = elements(&a);
+            for x in &elems {
+                assert_eq!(inv.apply(&iso.apply(x)), *x);
+            }
+            for x in elems.iter().step_by(7) {
+                for y in elems.iter().step_by(5) {
+                    assert_eq!(
+                        iso.apply(&(x.clone() * y.clone())),
+                        iso.apply(x) * iso.apply(y)
+                    );
+                    assert_eq!(
+                        iso.apply(&(x.clone() + y.clone())),
+                        iso.apply(x) + iso.apply(y)
+                    );
+                }
+            }
+            // x goes to a root of the first modulus
+            let f = a.modulus().clone();
+            let image = iso.apply(&a.gen());
+            let value = f
+                .coef
+                .iter()
+                .rev()
+                .fold(b.zero(), |acc, c| acc * image.clone() + b.embed(c));
+            assert!(value.is_zero());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a subfield")]
+    fn degree_must_divide() {
+        Embedding::new(&GF::new(2, 3), &GF::new(2, 4));
+    }
+}
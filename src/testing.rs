@@ -0,0 +1,21 @@
+//! Helpers shared by the unit tests.
+
+use crate::{FiniteField, MontModulus, GF};
+
+/// Every element of `gf`, in the index order of `GF::index_to_coef`.
+pub(crate) fn elements(gf: &GF) -> Vec<FiniteField> {
+    (0..gf.order() as usize)
+        .map(|i| gf.elem(&gf.index_to_coef(i)))
+        .collect()
+}
+
+/// Curve25519, p = 2^255 - 19.
+pub(crate) struct P25519;
+impl MontModulus<4> for P25519 {
+    const MODULUS: [u64; 4] = [
+        0xffffffffffffffed,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ];
+}
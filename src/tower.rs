@@ -0,0 +1,532 @@
+//! Extension towers F_q[x] / (f) over any field F_q, including other extensions.
+
+use crate::biguint::BigUint;
+use crate::{Field, FiniteField, Polynomial, GF};
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+/// The extension F_q[x] / (f) of degree k over a base field F_q implementing [`Field`],
+/// created once and used to construct elements.
+///
+/// Since the elements implement `Field` themselves, extensions can be stacked into towers.
+/// Moduli of the form x^2 - b and x^3 - b, as used for pairings, multiply and invert by
+/// Karatsuba-style formulas; other moduli use schoolbook multiplication and the extended
+/// Euclidean algorithm.
+/// ```
+/// use galois_field::*;
+/// struct P;
+/// impl MontModulus<4> for P {
+///     // base field of BN254
+///     const MODULUS: [u64; 4] = [
+///         0x3c208c16d87cfd47,
+///         0x97816a916871ca8d,
+///         0xb85045b68181585d,
+///         0x30644e72e131a029,
+///     ];
+/// }
+/// type Fp = MontFp<P, 4>;
+/// let c = |x: u64| Fp::new(x);
+/// // Fp2 = Fp[u] / (u^2 + 1), Fp6 = Fp2[v] / (v^3 - (u + 9)), Fp12 = Fp6[w] / (w^2 - v)
+/// let fp2 = ExtensionField::with_modulus(Polynomial { coef: vec![c(1), c(0), c(1)] });
+/// let xi = fp2.elem(&[c(9), c(1)]);
+/// let fp6 = ExtensionField::with_modulus(Polynomial {
+///     coef: vec![-xi.clone(), fp2.zero(), fp2.zero(), fp2.one()],
+/// });
+/// let fp12 = ExtensionField::with_modulus(Polynomial {
+///     coef: vec![-fp6.gen(), fp6.zero(), fp6.one()],
+/// });
+/// let a = fp12.elem(&[fp6.elem(&[fp2.gen(), xi]), fp6.one()]);
+/// assert_eq!(a.clone() * a.inv(), fp12.one());
+/// assert_eq!(fp12.gen().pow(2), fp12.elem(&[fp6.gen()]));
+/// ```
+#[derive(Clone)]
+pub struct ExtensionField<F: Field> {
+    inner: Arc<ExtensionInner<F>>,
+}
+
+struct ExtensionInner<F> {
+    // monic, irreducible over F
+    modulus: Polynomial<F>,
+    shape: Shape<F>,
+}
+
+// moduli with fast multiplication and inversion
+enum Shape<F> {
+    // x^2 - b
+    Quadratic(F),
+    // x^3 - b
+    Cubic(F),
+    General,
+}
+
+/// Element of an `ExtensionField`.
+#[derive(Clone)]
+pub struct ExtElement<F: Field> {
+    // exactly k coefficients in ascending order
+    coef: Vec<F>,
+    field: ExtensionField<F>,
+}
+
+impl ExtensionField<FiniteField> {
+    /// GF(q^k) over GF(q) = `base`, defined by the irreducible polynomial of
+    /// `GF::low_weight_irreducible`, so by x^k - b when there is one.
+    ///
+    /// Panics if k = 0.
+    /// ```
+    /// use galois_field::*;
+    /// // GF((2^2)^3), a field with 64 elements
+    /// let gf4 = GF::new(2, 2);
+    /// let field = ExtensionField::new(&gf4, 3);
+    /// assert_eq!(field.gen().order(), 64);
+    /// assert!(field.gen().pow(63).is_one());
+    /// ```
+    pub fn new(base: &GF, k: usize) -> ExtensionField<FiniteField> {
+        let modulus = base
+            .low_weight_irreducible(k)
+            .or_else(|| base.irreducible_polynomials(k).next())
+            .expect("an irreducible polynomial of every degree exists");
+        ExtensionField::from_monic(modulus)
+    }
+}
+
+impl<F: Field> ExtensionField<F> {
+    /// F_q[x] / (modulus) for an irreducible polynomial over F_q of degree at least 1.
+    ///
+    /// The modulus is made monic. Panics if it is not irreducible.
+    pub fn with_modulus(modulus: Polynomial<F>) -> ExtensionField<F> {
+        let modulus = modulus.clone().adjust_func();
+        assert!(modulus.coef.len() >= 2, "modulus must not be a constant");
+        assert!(modulus.is_irreducible(), "modulus must be irreducible");
+        let lead_inv = modulus.coef[modulus.coef.len() - 1].inv();
+        let monic = Polynomial {
+            coef: modulus
+                .coef
+                .into_iter()
+                .map(|c| c * lead_inv.clone())
+                .collect(),
+        };
+        ExtensionField::from_monic(monic)
+    }
+
+    /// F_q[x] / (x^k - b) for the first b among `candidates` making x^k - b irreducible,
+    /// e.g. a quadratic or cubic non-residue for the next level of a tower.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(7, 1);
+    /// // 1, 2 and 4 are squares mod 7
+    /// let field = ExtensionField::binomial(2, (1..7).map(|i| gf.elem(&[i]))).unwrap();
+    /// assert_eq!(field.gen().pow(2), field.elem(&[gf.elem(&[3])]));
+    /// ```
+    pub fn binomial(
+        k: usize,
+        candidates: impl IntoIterator<Item = F>,
+    ) -> Option<ExtensionField<F>> {
+        assert!(k >= 1, "degree of the extension must be at least 1");
+        candidates.into_iter().find_map(|b| {
+            let mut coef = vec![b.zero(); k + 1];
+            coef[k] = b.one();
+            coef[0] = -b;
+            let f = Polynomial { coef };
+            f.is_irreducible().then(|| ExtensionField::from_monic(f))
+        })
+    }
+
+    fn from_monic(modulus: Polynomial<F>) -> ExtensionField<F> {
+        let k = modulus.coef.len() - 1;
+        let binomial = modulus.coef[1..k].iter().all(|c| c.is_zero());
+        let shape = match k {
+            2 if binomial => Shape::Quadratic(-modulus.coef[0].clone()),
+            3 if binomial => Shape::Cubic(-modulus.coef[0].clone()),
+            _ => Shape::General,
+        };
+        ExtensionField {
+            inner: Arc::new(ExtensionInner { modulus, shape }),
+        }
+    }
+
+    /// Degree k of the extension over the base field.
+    pub fn degree(&self) -> usize {
+        self.inner.modulus.coef.len() - 1
+    }
+
+    /// The monic modulus over the base field.
+    pub fn modulus(&self) -> &Polynomial<F> {
+        &self.inner.modulus
+    }
+
+    /// The element sum coef\[i\] x^i, reduced modulo the modulus.
+    pub fn elem(&self, coef: &[F]) -> ExtElement<F> {
+        let mut coef = coef.to_vec();
+        if coef.len() > self.degree() {
+            coef = self.reduce(coef);
+        }
+        coef.resize(self.degree(), self.base_zero());
+        ExtElement {
+            coef,
+            field: self.clone(),
+        }
+    }
+
+    /// 0 of the field.
+    pub fn zero(&self) -> ExtElement<F> {
+        self.elem(&[])
+    }
+
+    /// 1 of the field.
+    pub fn one(&self) -> ExtElement<F> {
+        self.elem(&[self.base_zero().one()])
+    }
+
+    /// The class of x, a root of the modulus.
+    pub fn gen(&self) -> ExtElement<F> {
+        let zero = self.base_zero();
+        self.elem(&[zero.clone(), zero.one()])
+    }
+
+    fn base_zero(&self) -> F {
+        self.inner.modulus.coef[0].zero()
+    }
+
+    // c mod modulus, as exactly k coefficients
+    fn reduce(&self, mut c: Vec<F>) -> Vec<F> {
+        let k = self.degree();
+        let m = &self.inner.modulus.coef;
+        for i in (k..c.len()).rev() {
+            let top = c[i].clone();
+            if top.is_zero() {
+                continue;
+            }
+            for j in 0..k {
+                c[i - k + j] = c[i - k + j].clone() - top.clone() * m[j].clone();
+            }
+        }
+        c.truncate(k);
+        c
+    }
+
+    fn mul_coef(&self, a: &[F], b: &[F]) -> Vec<F> {
+        match &self.inner.shape {
+            Shape::Quadratic(beta) => {
+                let v0 = a[0].clone() * b[0].clone();
+                let v1 = a[1].clone() * b[1].clone();
+                let cross = (a[0].clone() + a[1].clone()) * (b[0].clone() + b[1].clone());
+                vec![v0.clone() + beta.clone() * v1.clone(), cross - v0 - v1]
+            }
+            Shape::Cubic(xi) => {
+                let v0 = a[0].clone() * b[0].clone();
+                let v1 = a[1].clone() * b[1].clone();
+                let v2 = a[2].clone() * b[2].clone();
+                let t12 = (a[1].clone() + a[2].clone()) * (b[1].clone() + b[2].clone());
+                let t01 = (a[0].clone() + a[1].clone()) * (b[0].clone() + b[1].clone());
+                let t02 = (a[0].clone() + a[2].clone()) * (b[0].clone() + b[2].clone());
+                vec![
+                    v0.clone() + xi.clone() * (t12 - v1.clone() - v2.clone()),
+                    t01 - v0.clone() - v1.clone() + xi.clone() * v2.clone(),
+                    t02 - v0 - v2 + v1,
+                ]
+            }
+            Shape::General => {
+                let mut c = vec![self.base_zero(); a.len() + b.len() - 1];
+                for (i, x) in a.iter().enumerate() {
+                    if x.is_zero() {
+                        continue;
+                    }
+                    for (j, y) in b.iter().enumerate() {
+                        c[i + j] = c[i + j].clone() + x.clone() * y.clone();
+                    }
+                }
+                self.reduce(c)
+            }
+        }
+    }
+
+    // inverse of a nonzero a
+    fn inv_coef(&self, a: &[F]) -> Vec<F> {
+        match &self.inner.shape {
+            Shape::Quadratic(beta) => {
+                // (a0 + a1 x)(a0 - a1 x) = a0^2 - b a1^2
+                let norm = a[0].clone() * a[0].clone() - beta.clone() * a[1].clone() * a[1].clone();
+                let t = norm.inv();
+                vec![a[0].clone() * t.clone(), -(a[1].clone() * t)]
+            }
+            Shape::Cubic(xi) => {
+                let c0 = a[0].clone() * a[0].clone() - xi.clone() * a[1].clone() * a[2].clone();
+                let c1 = xi.clone() * a[2].clone() * a[2].clone() - a[0].clone() * a[1].clone();
+                let c2 = a[1].clone() * a[1].clone() - a[0].clone() * a[2].clone();
+                let norm = a[0].clone() * c0.clone()
+                    + xi.clone() * (a[2].clone() * c1.clone() + a[1].clone() * c2.clone());
+                let t = norm.inv();
+                vec![c0 * t.clone(), c1 * t.clone(), c2 * t]
+            }
+            Shape::General => {
+                // r0 = t0 * a, r1 = t1 * a (mod modulus)
+                let zero = self.base_zero();
+                let mut r0 = self.inner.modulus.clone();
+                let mut r1 = Polynomial { coef: a.to_vec() }.adjust_func();
+                let mut t0 = Polynomial {
+                    coef: vec![zero.clone()],
+                };
+                let mut t1 = Polynomial {
+                    coef: vec![zero.one()],
+                };
+                while !r1.coef.iter().all(|c| c.is_zero()) {
+                    let q = r0.clone() / r1.clone();
+                    let r = r0 - q.clone() * r1.clone();
+                    let t = t0 - q * t1.clone();
+                    (r0, r1) = (r1, r);
+                    (t0, t1) = (t1, t);
+                }
+                // r0 is a nonzero constant since the modulus is irreducible
+                let c_inv = r0.coef[0].inv();
+                let coef: Vec<F> = t0.coef.into_iter().map(|t| t * c_inv.clone()).collect();
+                let mut coef = self.reduce(coef);
+                coef.resize(self.degree(), zero);
+                coef
+            }
+        }
+    }
+}
+
+impl<F: Field> PartialEq for ExtensionField<F> {
+    fn eq(&self, other: &ExtensionField<F>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) || self.inner.modulus == other.inner.modulus
+    }
+}
+
+impl<F: Field> fmt::Debug for ExtensionField<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ExtensionField({:?})", self.inner.modulus.coef)
+    }
+}
+
+impl<F: Field> ExtElement<F> {
+    /// Coefficients over the base field in ascending order, exactly k of them.
+    pub fn coef(&self) -> &[F] {
+        &self.coef
+    }
+
+    /// The field that the element belongs to.
+    pub fn field(&self) -> &ExtensionField<F> {
+        &self.field
+    }
+
+    fn with_coef(&self, coef: Vec<F>) -> ExtElement<F> {
+        ExtElement {
+            coef,
+            field: self.field.clone(),
+        }
+    }
+
+    fn assert_same(&self, other: &ExtElement<F>) {
+        assert!(
+            self.field == other.field,
+            "elements of different fields: {:?} and {:?}",
+            self.field,
+            other.field
+        );
+    }
+}
+
+impl<F: Field> PartialEq for ExtElement<F> {
+    fn eq(&self, other: &ExtElement<F>) -> bool {
+        self.field == other.field && self.coef == other.coef
+    }
+}
+
+impl<F: Field> fmt::Debug for ExtElement<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.coef)
+    }
+}
+
+impl<F: Field> ops::Add for ExtElement<F> {
+    type Output = ExtElement<F>;
+    fn add(self, other: ExtElement<F>) -> ExtElement<F> {
+        self.assert_same(&other);
+        let coef = self.coef.iter().zip(other.coef);
+        self.with_coef(coef.map(|(a, b)| a.clone() + b).collect())
+    }
+}
+
+impl<F: Field> ops::Sub for ExtElement<F> {
+    type Output = ExtElement<F>;
+    fn sub(self, other: ExtElement<F>) -> ExtElement<F> {
+        self.assert_same(&other);
+        let coef = self.coef.iter().zip(other.coef);
+        self.with_coef(coef.map(|(a, b)| a.clone() - b).collect())
+    }
+}
+
+impl<F: Field> ops::Mul for ExtElement<F> {
+    type Output = ExtElement<F>;
+    fn mul(self, other: ExtElement<F>) -> ExtElement<F> {
+        self.assert_same(&other);
+        self.with_coef(self.field.mul_coef(&self.coef, &other.coef))
+    }
+}
+
+impl<F: Field> ops::Div for ExtElement<F> {
+    type Output = ExtElement<F>;
+    // As with `Fp`, dividing by 0 panics.
+    fn div(self, other: ExtElement<F>) -> ExtElement<F> {
+        self.assert_same(&other);
+        assert!(!other.is_zero(), "division by zero");
+        let inv = self.field.inv_coef(&other.coef);
+        self.with_coef(self.field.mul_coef(&self.coef, &inv))
+    }
+}
+
+impl<F: Field> ops::Neg for ExtElement<F> {
+    type Output = ExtElement<F>;
+    fn neg(self) -> ExtElement<F> {
+        let coef = self.coef.iter().map(|a| -a.clone()).collect();
+        self.with_coef(coef)
+    }
+}
+
+impl<F: Field> Field for ExtElement<F> {
+    fn zero(&self) -> ExtElement<F> {
+        self.field.zero()
+    }
+    fn one(&self) -> ExtElement<F> {
+        self.field.one()
+    }
+    fn inv(&self) -> ExtElement<F> {
+        assert!(!self.is_zero(), "0 has no inverse");
+        self.with_coef(self.field.inv_coef(&self.coef))
+    }
+    fn characteristic(&self) -> u64 {
+        self.coef[0].characteristic()
+    }
+    /// Panics if q^k does not fit in u128, see `order_big`.
+    fn order(&self) -> u128 {
+        u32::try_from(self.field.degree())
+            .ok()
+            .and_then(|k| self.coef[0].order().checked_pow(k))
+            .expect("order does not fit in u128")
+    }
+    fn characteristic_big(&self) -> Vec<u64> {
+        self.coef[0].characteristic_big()
+    }
+    fn order_big(&self) -> Vec<u64> {
+        let q = BigUint::from_limbs(&self.coef[0].order_big());
+        let order = (0..self.field.degree()).fold(BigUint::one(), |acc, _| &acc * &q);
+        order.limbs().to_vec()
+    }
+    fn is_zero(&self) -> bool {
+        self.coef.iter().all(|c| c.is_zero())
+    }
+    fn is_one(&self) -> bool {
+        self.coef[0].is_one() && self.coef[1..].iter().all(|c| c.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::elements;
+    use crate::{MontFp, MontModulus};
+
+    // every element of GF(q^k) over GF(q), the constant coefficient running fastest
+    fn tower_elements(
+        field: &ExtensionField<FiniteField>,
+        base: &GF,
+    ) -> Vec<ExtElement<FiniteField>> {
+        let base_elements = elements(base);
+        let mut coefs: Vec<Vec<FiniteField>> = vec![vec![]];
+        for _ in 0..field.degree() {
+            coefs = base_elements
+                .iter()
+                .flat_map(|b| {
+                    coefs.iter().map(move |c| {
+                        let mut c = c.clone();
+                        c.push(b.clone());
+                        c
+                    })
+                })
+                .collect();
+        }
+        coefs.iter().map(|c| field.elem(c)).collect()
+    }
+
+    #[test]
+    fn small_towers() {
+        // GF((3^2)^2) with a quadratic binomial, GF((2^2)^3) with a cubic one, GF((2^2)^2) without
+        for (p, m, k) in [(3, 2, 2), (2, 2, 3), (2, 2, 2)] {
+            let base = GF::new(p, m);
+            let field = ExtensionField::new(&base, k);
+            let fast = matches!(field.inner.shape, Shape::Quadratic(_) | Shape::Cubic(_));
+            assert_eq!(fast, (p, k) != (2, 2));
+            let all = tower_elements(&field, &base);
+            let order = field.one().order();
+            assert_eq!(all.len() as u128, order);
+            // the multiplicative group has exactly q^k - 1 elements
+            for a in all.iter().filter(|a| !a.is_zero()) {
+                assert!(a.pow(order - 1).is_one());
+                assert_eq!(a.clone() * a.inv(), field.one());
+            }
+            for a in all.iter().step_by(5) {
+                for b in all.iter().step_by(3) {
+                    // the fast paths agree with multiplication of polynomials mod the modulus
+                    let product = Polynomial {
+                        coef: a.coef().to_vec(),
+                    } * Polynomial {
+                        coef: b.coef().to_vec(),
+                    } % field.modulus().clone();
+                    assert_eq!(a.clone() * b.clone(), field.elem(&product.coef));
+                    if !b.is_zero() {
+                        assert_eq!((a.clone() / b.clone()) * b.clone(), a.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        let field = ExtensionField::new(&GF::new(3, 2), 2);
+        let _ = field.one() / field.zero();
+    }
+
+    #[test]
+    fn pairing_tower() {
+        // base field of BN254
+        struct P;
+        impl MontModulus<4> for P {
+            const MODULUS: [u64; 4] = [
+                0x3c208c16d87cfd47,
+                0x97816a916871ca8d,
+                0xb85045b68181585d,
+                0x30644e72e131a029,
+            ];
+        }
+        type Fp = MontFp<P, 4>;
+        let c = |x: u64| Fp::new(x);
+        let fp2 = ExtensionField::binomial(2, (1..).map(|x| -c(x))).unwrap();
+        // u^2 = -1
+        assert_eq!(fp2.gen().pow(2), -fp2.one());
+        let xi = fp2.elem(&[c(9), c(1)]);
+        let fp6 = ExtensionField::binomial(3, [xi.clone()]).unwrap();
+        let fp12 = ExtensionField::binomial(2, [fp6.gen()]).unwrap();
+
+        let a = fp12.elem(&[
+            fp6.elem(&[xi.clone(), fp2.gen(), fp2.elem(&[c(5), c(7)])]),
+            fp6.elem(&[fp2.one(), xi.clone() * xi.clone()]),
+        ]);
+        let b = fp12.elem(&[fp6.gen(), fp6.elem(&[fp2.elem(&[c(3)])])]);
+        assert_eq!(a.clone() * a.inv(), fp12.one());
+        assert_eq!((a.clone() * b.clone()) / b.clone(), a);
+        // the Frobenius map is a ring homomorphism
+        assert_eq!(
+            (a.clone() * b.clone()).frobenius(),
+            a.frobenius() * b.frobenius()
+        );
+        assert_eq!(
+            (a.clone() + b.clone()).frobenius(),
+            a.frobenius() + b.frobenius()
+        );
+        assert_eq!(fp12.one().order_big().len(), 48);
+    }
+}
@@ -0,0 +1,224 @@
+//! Trace, norm, conjugates and minimal polynomial of elements of GF(p^n), absolute and relative
+//! to an intermediate field GF(p^m).
+
+use crate::{coefficient_modulus, Element, FiniteField, Polynomial};
+
+impl FiniteField {
+    /// Absolute trace a + a^p + ... + a^(p^(n-1)), an element of F_p.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// // Tr(x) = x + x^2 + x^4 + x^8 = 0 for x^4 + x + 1
+    /// assert_eq!(gf.gen().trace(), GF::new(2, 1).zero());
+    /// assert_eq!(gf.gen().pow(3).trace(), GF::new(2, 1).one());
+    /// ```
+    pub fn trace(&self) -> FiniteField {
+        self.to_prime_field(self.trace_over(1))
+    }
+
+    /// Absolute norm a * a^p * ... * a^(p^(n-1)) = a^((p^n - 1) / (p - 1)), an element of F_p.
+    pub fn norm(&self) -> FiniteField {
+        self.to_prime_field(self.norm_over(1))
+    }
+
+    /// The distinct conjugates a, a^p, a^(p^2), ... of a over F_p, i.e. its orbit under Frobenius.
+    pub fn conjugates(&self) -> Vec<FiniteField> {
+        self.conjugates_over(1)
+    }
+
+    /// The monic polynomial over F_p of least degree having a as a root,
+    /// the product of x - c over the conjugates c.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(2, 4);
+    /// let f = gf.gen().minimal_polynomial();
+    /// assert_eq!(f, *gf.modulus());
+    /// // x^5 lies in GF(2^2), with minimal polynomial x^2 + x + 1
+    /// let f = gf.gen().pow(5).minimal_polynomial();
+    /// assert_eq!(f.coef.len(), 3);
+    /// ```
+    pub fn minimal_polynomial(&self) -> Polynomial {
+        let f = self.minimal_polynomial_over(1);
+        Polynomial {
+            coef: f
+                .coef
+                .iter()
+                .map(|c| self.to_prime_field(c.clone()))
+                .collect(),
+        }
+    }
+
+    /// Relative trace to GF(p^m), the sum of a^(p^(mi)) for 0 <= i < n/m.
+    ///
+    /// The result is an element of the same field, lying in the subfield GF(p^m). Panics unless m divides n.
+    /// ```
+    /// use galois_field::*;
+    /// let gf = GF::new(3, 4);
+    /// let a = gf.elem(&[1, 2, 0, 1]);
+    /// let t = a.trace_over(2);
+    /// assert!(gf.is_in_subfield(&t, 2));
+    /// // transitivity: Tr_{9/3}(Tr_{81/9}(a)) = Tr_{81/3}(a)
+    /// assert_eq!(t.clone() + t.frobenius(), gf.embed(&a.trace()));
+    /// ```
+    pub fn trace_over(&self, m: usize) -> FiniteField {
+        let orbit = self.relative_frobenius_orbit(m);
+        orbit.into_iter().reduce(|acc, c| acc + c).unwrap()
+    }
+
+    /// Relative norm to GF(p^m), the product of a^(p^(mi)) for 0 <= i < n/m.
+    ///
+    /// The result is an element of the same field, lying in the subfield GF(p^m). Panics unless m divides n.
+    pub fn norm_over(&self, m: usize) -> FiniteField {
+        let orbit = self.relative_frobenius_orbit(m);
+        orbit.into_iter().reduce(|acc, c| acc * c).unwrap()
+    }
+
+    /// The distinct conjugates a, a^(p^m), a^(p^(2m)), ... of a over GF(p^m).
+    ///
+    /// Panics unless m divides n.
+    pub fn conjugates_over(&self, m: usize) -> Vec<FiniteField> {
+        let mut orbit = self.relative_frobenius_orbit(m);
+        let len = (1..orbit.len())
+            .find(|&i| orbit[i] == *self)
+            .unwrap_or(orbit.len());
+        orbit.truncate(len);
+        orbit
+    }
+
+    /// The monic minimal polynomial of a over GF(p^m), with coefficients given as elements of
+    /// the same field lying in the subfield.
+    ///
+    /// Panics unless m divides n.
+    pub fn minimal_polynomial_over(&self, m: usize) -> Polynomial {
+        let one = self.get_1();
+        self.conjugates_over(m).into_iter().fold(
+            Polynomial {
+                coef: vec![one.clone()],
+            },
+            |f, c| {
+                f * Polynomial {
+                    coef: vec![-c, one.clone()],
+                }
+            },
+        )
+    }
+
+    // a, a^(p^m), ..., a^(p^(m(n/m - 1)))
+    fn relative_frobenius_orbit(&self, m: usize) -> Vec<FiniteField> {
+        let n = match &self.element {
+            Element::PrimeField { .. } => 1,
+            Element::GaloisField { field, .. } => field.degree(),
+        };
+        assert!(
+            m >= 1 && n.is_multiple_of(m),
+            "GF({}^{}) has no subfield GF({}^{})",
+            self.char,
+            n,
+            self.char,
+            m
+        );
+        let mut orbit = vec![self.clone()];
+        for _ in 1..n / m {
+            let mut c = orbit[orbit.len() - 1].clone();
+            for _ in 0..m {
+                c = c.frobenius();
+            }
+            orbit.push(c);
+        }
+        orbit
+    }
+
+    // an element of GF(p^n) lying in F_p as an element of F_p
+    fn to_prime_field(&self, a: FiniteField) -> FiniteField {
+        let element = match a.element {
+            Element::PrimeField { element } => element,
+            Element::GaloisField { element, .. } => {
+                assert!(element.len() <= 1, "{:?} is not in F_{}", element, a.char);
+                element.first().copied().unwrap_or(0)
+            }
+        };
+        FiniteField {
+            char: self.char,
+            element: Element::PrimeField {
+                element: element.rem_euclid(coefficient_modulus(self.char)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::elements;
+    use crate::{Field, FiniteField, Polynomial, GF};
+
+    // f(a) for f over F_p
+    fn evaluate(gf: &GF, f: &Polynomial, a: &FiniteField) -> FiniteField {
+        f.coef
+            .iter()
+            .rev()
+            .fold(gf.zero(), |acc, c| acc * a.clone() + gf.embed(c))
+    }
+
+    #[test]
+    fn absolute_invariants() {
+        for (p, n) in [(2, 4), (2, 6), (3, 3), (5, 2)] {
+            let gf = GF::new(p, n);
+            let fp = GF::new(p, 1);
+            let q = p as u128;
+            let mut trace_counts = vec![0; p as usize];
+            for a in elements(&gf) {
+                // brute force by exponentiation
+                let powers: Vec<FiniteField> = (0..n as u32).map(|i| a.pow(q.pow(i))).collect();
+                let trace = powers.iter().cloned().reduce(|x, y| x + y).unwrap();
+                assert_eq!(gf.embed(&a.trace()), trace);
+                assert_eq!(gf.embed(&a.norm()), a.pow((q.pow(n as u32) - 1) / (q - 1)));
+                let t = a.trace();
+                trace_counts[(0..p as i64).position(|i| fp.elem(&[i]) == t).unwrap()] += 1;
+
+                // the least degree monic polynomial vanishing at a
+                let d = (1..=n)
+                    .find(|&d| {
+                        fp.monic_polynomials(d)
+                            .any(|f| evaluate(&gf, &f, &a).is_zero())
+                    })
+                    .unwrap();
+                let f = a.minimal_polynomial();
+                assert_eq!(f.coef.len() - 1, d);
+                assert!(evaluate(&gf, &f, &a).is_zero());
+                let conjugates = a.conjugates();
+                assert_eq!(conjugates.len(), d);
+                for c in &conjugates {
+                    assert!(evaluate(&gf, &f, c).is_zero());
+                }
+            }
+            // the trace is onto F_p and balanced
+            assert!(trace_counts.iter().all(|&c| c as u128 == gf.order() / q));
+        }
+    }
+
+    #[test]
+    fn relative_invariants() {
+        for (p, n, m) in [(2, 6, 2), (2, 6, 3), (3, 4, 2), (2, 4, 4)] {
+            let gf = GF::new(p, n);
+            let qm = (p as u128).pow(m as u32);
+            for a in elements(&gf).into_iter().step_by(3) {
+                let t = a.trace_over(m);
+                let norm = a.norm_over(m);
+                assert!(gf.is_in_subfield(&t, m));
+                assert!(gf.is_in_subfield(&norm, m));
+                assert_eq!(norm, a.pow((gf.order() - 1) / (qm - 1)));
+                // Tr_{p^n/p} = Tr_{p^m/p} o Tr_{p^n/p^m}
+                let mut conjugates = vec![t.clone()];
+                for _ in 1..m {
+                    conjugates.push(conjugates[conjugates.len() - 1].frobenius());
+                }
+                let tt = conjugates.into_iter().reduce(|x, y| x + y).unwrap();
+                assert_eq!(gf.embed(&a.trace()), tt);
+                let mut f = a.minimal_polynomial_over(m);
+                assert_eq!(f.coef.len() - 1, a.conjugates_over(m).len());
+                assert!(f.coef.iter().all(|c| gf.is_in_subfield(c, m)));
+                assert!(f.assign_value(a.clone()).is_zero());
+            }
+        }
+    }
+}